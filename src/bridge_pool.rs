@@ -8,13 +8,15 @@
 
 // extern crate bigint;
 // use bigint::U256;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 extern crate alloc;
 use alloc::string::String;
-use secp256k1::Secp256k1;
 use crate::crypto_utils::EcdsaSig;
 use crate::crypto_utils::ecdsa_recover;
+use crate::crypto_utils::eth_message_hash;
+use crate::crypto_utils::EthAddress;
 use crate::crypto_utils::keccak256_hash;
+use crate::crypto_utils::verify_merkle_proof;
 
 use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use casper_erc20::{
@@ -25,46 +27,210 @@ use casper_erc20::{
     },
     Address, ERC20,
 };
-use casper_types::{CLValue, U256};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::FromBytes,
+    crypto, system::CallStackElement, CLValue, PublicKey, Signature, U256,
+};
 
 
 // I don't have access to this interface/trait
 #[allow(dead_code)]
 
-// you can change the number of bytes or completely the type
-type SignerAddress = [u8; 32];
+// The signer is either an Ethereum style address recovered from an ECDSA signature, or a
+// Casper account whose public key travels alongside the signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignerAddress {
+    Evm(EthAddress),
+    Casper(AccountHash),
+}
+
+impl SignerAddress {
+    fn is_zero(&self) -> bool {
+        match self {
+            SignerAddress::Evm(address) => address.is_zero(),
+            SignerAddress::Casper(account_hash) => account_hash.value() == ZERO_ADDRESS,
+        }
+    }
+}
 type Bytes = [u8; 32];
 type Bytes8 = [u8];
 type Liquidity = HashMap<Address, HashMap<Address, U256>>;
+type WithdrawalQueues = HashMap<Address, VecDeque<PendingWithdrawal>>;
+// (token, payee, amount, salt, signature) as accepted by withdraw_signed
+pub type SignedWithdrawal = (Address, Address, U256, Bytes, Vec<u8>);
 const ZERO_ADDRESS: [u8; 32] = [0u8; 32]; // address(0)
 const ZERO: U256 = U256::from(0);
 const TEN_THOUSAND: U256 = U256::from(10000);
 const NAME: &'static str = "FERRUM_TOKEN_BRIDGE_POOL";
 const VERSION: &'static str = "000.001";
+// Casper addresses are 32 byte hashes, so they are typed and encoded as bytes32 rather than
+// as 20 byte EVM addresses. The pool's contract package hash is the domain salt.
+const EIP712_DOMAIN_TYPE: &'static str =
+    "EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)";
+const WITHDRAW_SIGNED_TYPE: &'static str =
+    "WithdrawSigned(bytes32 token,bytes32 payee,uint256 amount,bytes32 salt)";
+const WITHDRAW_LEAF_TYPE: &'static str =
+    "WithdrawLeaf(bytes32 token,bytes32 payee,uint256 amount,bytes32 salt)";
+const WITHDRAW_ROOT_TYPE: &'static str =
+    "WithdrawRoot(bytes32 root,uint64 leafCount,uint64 expiry)";
+const REVOKE_SALT_TYPE: &'static str = "RevokeSalt(bytes32 digest)";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BridgePool {
+    // EIP-712 domain separator binding signatures to this chain and pool
+    domain_separator: Bytes,
     signer: SignerAddress,
-    usedHashes: HashMap<Bytes, bool>,
+    usedHashes: HashMap<Bytes, DigestStatus>,
     fees: HashMap<Address, U256>,
+    signer_manager: Address,
     feeDistributor: Address,
-    liquidities: Liquidity
+    liquidities: Liquidity,
+    // tokens actually held by the pool, i.e. IERC20(token).balanceOf(address(this))
+    pool_balances: HashMap<Address, U256>,
+    // FIFO of unfilled withdrawals per token, served as liquidity comes back in
+    withdrawal_queues: WithdrawalQueues,
+    // signed merkle roots of batched withdrawals, their expiry (unix seconds) and leaf count
+    withdrawal_roots: HashMap<Bytes, (u64, u64)>,
+    // per root, bitmap of claimed leaf indexes in 64 bit words
+    claimed_leaves: HashMap<Bytes, HashMap<u64, u64>>,
+    // signer being rotated out and the time (unix seconds) until which it is still accepted
+    previous_signer: Option<(SignerAddress, u64)>,
+    // signers whose signatures are over the EIP-191 personal_sign wrapping of the digest
+    personal_sign_signers: HashSet<SignerAddress>,
+    events: Vec<BridgeEvent>,
 }
 
+// Why a withdraw_signed digest can no longer be used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigestStatus {
+    Executed,
+    Revoked,
+}
 
-// constructor () EIP712(NAME, VERSION) { }
+#[derive(Debug, Clone, PartialEq)]
+pub enum BridgeEvent {
+    SignerRotationStarted {
+        old_signer: SignerAddress,
+        new_signer: SignerAddress,
+        overlap_until: u64,
+    },
+    SignerRotationCompleted {
+        old_signer: SignerAddress,
+        new_signer: SignerAddress,
+    },
+    SaltRevoked {
+        digest: Bytes,
+    },
+}
+
+// Remainder of a withdrawal the pool could not pay out at the time it was requested.
+#[derive(Debug, Clone)]
+pub struct PendingWithdrawal {
+    recipient: Address,
+    amount: U256,
+}
 
 impl BridgePool {
-    pub fn set_signer(&mut self, signer: Address)-> Result<(), String>{
-        if signer == ZERO_ADDRESS {
+    // constructor () EIP712(NAME, VERSION) { }
+    // `pool` is the contract package the pool is installed as, `chain_id` identifies the network.
+    pub fn new(chain_id: U256, pool: Address) -> Self {
+        BridgePool {
+            domain_separator: domain_separator(chain_id, pool),
+            signer: SignerAddress::Evm(EthAddress::default()),
+            usedHashes: HashMap::new(),
+            fees: HashMap::new(),
+            signer_manager: Address::from(AccountHash::new(ZERO_ADDRESS)),
+            feeDistributor: Address::from(AccountHash::new(ZERO_ADDRESS)),
+            liquidities: HashMap::new(),
+            pool_balances: HashMap::new(),
+            withdrawal_queues: HashMap::new(),
+            withdrawal_roots: HashMap::new(),
+            claimed_leaves: HashMap::new(),
+            previous_signer: None,
+            personal_sign_signers: HashSet::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn domain_separator(&self) -> Bytes {
+        self.domain_separator
+    }
+
+    // Replaces the signer at once. Not allowed while a rotation is still in its overlap window,
+    // since that would silently drop the old signer the rotation promised to keep accepting.
+    pub fn set_signer(&mut self, signer: SignerAddress)-> Result<(), String>{
+        if signer.is_zero() {
             return Err("Bad Signer".into());
         }
+        self.expire_previous_signer();
+        if self.previous_signer.is_some() {
+            return Err("BridgePool: rotation in progress".into());
+        }
 
         self.signer = signer;
         
         Ok(())
     }
 
+    // Switches to `signer` but keeps accepting signatures of the current one until
+    // `overlap_until` (block time, unix seconds), so withdrawals signed before the rotation can
+    // still land.
+    pub fn rotate_signer(&mut self, signer: SignerAddress, overlap_until: u64) -> Result<(), String> {
+        if signer.is_zero() {
+            return Err("Bad Signer".into());
+        }
+        self.expire_previous_signer();
+        if overlap_until <= block_time() {
+            return Err("BridgePool: overlap must end in the future".into());
+        }
+        if self.previous_signer.is_some() {
+            return Err("BridgePool: rotation already in progress".into());
+        }
+
+        let old_signer = self.signer;
+        self.previous_signer = Some((old_signer, overlap_until));
+        self.signer = signer;
+        self.events.push(BridgeEvent::SignerRotationStarted {
+            old_signer,
+            new_signer: signer,
+            overlap_until,
+        });
+
+        Ok(())
+    }
+
+    // Drops the previous signer once its overlap window is over. Called on every signature
+    // check, but can be poked by anyone to close the rotation explicitly.
+    pub fn expire_previous_signer(&mut self) {
+        if let Some((old_signer, overlap_until)) = self.previous_signer {
+            if block_time() >= overlap_until {
+                self.previous_signer = None;
+                self.events.push(BridgeEvent::SignerRotationCompleted {
+                    old_signer,
+                    new_signer: self.signer,
+                });
+            }
+        }
+    }
+
+    // Lets `signer` sign with wallets that only support personal_sign (EIP-191).
+    pub fn set_personal_sign(&mut self, signer: SignerAddress, enabled: bool) {
+        if enabled {
+            self.personal_sign_signers.insert(signer);
+        } else {
+            self.personal_sign_signers.remove(&signer);
+        }
+    }
+
+    pub fn previous_signer(&self) -> Option<(SignerAddress, u64)> {
+        self.previous_signer
+    }
+
+    pub fn events(&self) -> &[BridgeEvent] {
+        &self.events
+    }
+
     pub fn set_fee(&mut self, token: Address, fee_10000: U256) -> Result<(), String>{
         if token == ZERO_ADDRESS {
             return Err("Bad Token".into());
@@ -75,6 +241,10 @@ impl BridgePool {
         Ok(())
     }
 
+    pub fn set_signer_manager(&mut self, signer_manager: Address) {
+        self.signer_manager = signer_manager;
+    }
+
     pub fn set_fee_distributor(&mut self, fee_distributor: Address) {
         self.feeDistributor = fee_distributor;
     }
@@ -120,8 +290,8 @@ impl BridgePool {
     let zero: U256 = U256::from(zero_0);
     let ten_thousand = U256::from(ten_000);
 
-    let actual_amount: U256 = amount;
-    let mut fee;
+    let mut actual_amount: U256 = amount;
+    let fee;
     let fee_distributor: Address = self.feeDistributor;
     
     if fee_distributor == ZERO_ADDRESS {
@@ -134,6 +304,7 @@ impl BridgePool {
         }
     }
     // IERC20(token).transferFrom(from, address(this), actualAmount);
+    self.credit_pool(token, actual_amount);
     // emit BridgeSwap(from, token, targetNetwork, targetToken, targetAddress, actualAmount, fee);
     return actual_amount;
 }
@@ -146,11 +317,12 @@ pub fn add_liquidity(&mut self, sender: Address, token: Address, amount: U256) -
     }
     // amount = SafeAmount.safeTransferFrom(token, msg.sender, address(this), amount);
 
-    if let Some(inner_hash_map) =  self.liquidities.get_mut(&token) {
-        let inner_hash_value = inner_hash_map.get(&sender).unwrap_or(&ZERO);
-        let result = *inner_hash_value + amount;
-        let _ = inner_hash_map.insert(sender, result);
-    }
+    let inner_hash_map = self.liquidities.entry(token).or_default();
+    let inner_hash_value = inner_hash_map.get(&sender).unwrap_or(&ZERO);
+    let result = *inner_hash_value + amount;
+    let _ = inner_hash_map.insert(sender, result);
+
+    self.credit_pool(token, amount);
 
     Ok(())
 
@@ -167,33 +339,110 @@ pub fn remove_liquidity_ifpossible(
         return Err("Amount must be positive and bad token provided".into());
     }
 
-    let liq = self.liquidities.get(&token).unwrap().get(&sender).unwrap();
+    let liq = self
+        .liquidities
+        .get(&token)
+        .and_then(|liquidities| liquidities.get(&sender))
+        .ok_or("BridgePool: no liquidity for token")?;
     
-    if liq <= &amount {
+    if liq < &amount {
         return Err("Not enough Liquidity".into());
     }
     
-    let balance = ERC20::default().balance_of(sender);
-    // let balance: U256 = ERC20(&token).balanceOf(sender);
+    // let balance: U256 = ERC20(&token).balanceOf(address(this));
+    let balance = self.pool_balance(token);
 
     // uint256 actualLiq = balance > amount ? amount : balance;
     let actual_liq = if balance > amount { amount } else { balance };
 
-    // liquidities[token][msg.sender] = liquidities[token][msg.sender].sub(actualLiq);
-
+    // The whole amount leaves the LP's position; whatever the pool could not pay
+    // right now is queued and paid out once liquidity comes back.
     if let Some(inner_hash_map) =  self.liquidities.get_mut(&token) {
         let inner_hash_value = inner_hash_map.get(&sender).unwrap_or(&ZERO);
-        let result = *inner_hash_value - actual_liq;
+        let result = *inner_hash_value - amount;
         let _ = inner_hash_map.insert(sender, result);
     }
 
     if actual_liq != ZERO {
+        self.debit_pool(token, actual_liq);
         // IERC20(token).safeTransfer(msg.sender, actualLiq);
         // emit BridgeLiquidityRemoved(sender, token, amount);
     }
+    if actual_liq < amount {
+        self.enqueue_withdrawal(token, sender, amount - actual_liq);
+    }
     Ok(actual_liq)
 }
 
+pub fn pool_balance(&self, token: Address) -> U256 {
+    *self.pool_balances.get(&token).unwrap_or(&ZERO)
+}
+
+// Position (0 = next to be served) of the first queued withdrawal of `account` for `token`.
+pub fn withdrawal_queue_position(&self, token: Address, account: Address) -> Option<usize> {
+    self.withdrawal_queues
+        .get(&token)?
+        .iter()
+        .position(|pending| pending.recipient == account)
+}
+
+// Total amount of `token` still owed to `account` by the withdrawal queue.
+pub fn pending_withdrawal(&self, token: Address, account: Address) -> U256 {
+    match self.withdrawal_queues.get(&token) {
+        Some(queue) => queue
+            .iter()
+            .filter(|pending| pending.recipient == account)
+            .fold(ZERO, |total, pending| total + pending.amount),
+        None => ZERO,
+    }
+}
+
+fn enqueue_withdrawal(&mut self, token: Address, recipient: Address, amount: U256) {
+    self.withdrawal_queues
+        .entry(token)
+        .or_default()
+        .push_back(PendingWithdrawal { recipient, amount });
+    // emit BridgeWithdrawalQueued(recipient, token, amount);
+}
+
+fn credit_pool(&mut self, token: Address, amount: U256) {
+    let balance = self.pool_balance(token);
+    self.pool_balances.insert(token, balance + amount);
+    self.serve_withdrawal_queue(token);
+}
+
+fn debit_pool(&mut self, token: Address, amount: U256) {
+    let balance = self.pool_balance(token);
+    self.pool_balances.insert(token, balance - amount);
+}
+
+// Pays out queued withdrawals for `token` in FIFO order from whatever the pool holds.
+// The head of the queue may be filled partially, in which case it keeps its position.
+fn serve_withdrawal_queue(&mut self, token: Address) {
+    let mut available = self.pool_balance(token);
+    let queue = match self.withdrawal_queues.get_mut(&token) {
+        Some(queue) => queue,
+        None => return,
+    };
+
+    while available != ZERO {
+        let head = match queue.front_mut() {
+            Some(head) => head,
+            None => break,
+        };
+        let paid = if available > head.amount { head.amount } else { available };
+        // IERC20(token).safeTransfer(head.recipient, paid);
+        // emit BridgeWithdrawalServed(head.recipient, token, paid);
+        head.amount = head.amount - paid;
+        available = available - paid;
+        if head.amount == ZERO {
+            queue.pop_front();
+        }
+    }
+
+    self.pool_balances.insert(token, available);
+}
+
 pub fn liquidity(
     &mut self, 
     token: Address,
@@ -215,47 +464,671 @@ pub fn withdraw_signed(
     salt: Bytes,
     signature: &[u8]
 ) -> Result<Bytes, String> {
-    let digest: Bytes;
+    self.expire_previous_signer();
+    let digest = self.check_withdraw_signed(token, payee, amount, salt, signature)?;
+    self.apply_withdraw_signed(digest, token, payee, amount);
+    Ok(digest)
+}
 
+// Everything withdraw_signed checks, without touching state.
+fn check_withdraw_signed(
+    &self,
+    token: Address,
+    payee: Address,
+    amount: U256,
+    salt: Bytes,
+    signature: &[u8]
+) -> Result<Bytes, String> {
+    let digest = withdraw_signed_digest(&self.domain_separator, token, payee, amount, salt);
+
+    if self.usedHashes.contains_key(&digest) { 
+        return Err("Message already used".into());
+    }
+    self.check_signer(&digest, signature)?;
+    Ok(digest)
+}
+
+fn apply_withdraw_signed(&mut self, digest: Bytes, token: Address, payee: Address, amount: U256) {
+    self.usedHashes.insert(digest, DigestStatus::Executed);
+    self.pay_out(token, payee, amount);
+    // emit TransferBySignature(digest, _signer, payee, token, amount);
+}
+
+fn verify_signer(&mut self, digest: &Bytes, signature: &[u8]) -> Result<(), String> {
+    self.expire_previous_signer();
+    self.check_signer(digest, signature)
+}
 
-    // digest = _hashTypedDataV4(keccak256(abi.encode(
-    //   keccak256("WithdrawSigned(address token, address payee,uint256 amount,bytes32 salt)"),
-    //      token, payee, amount, salt)));
+fn check_signer(&self, digest: &Bytes, signature: &[u8]) -> Result<(), String> {
+    let _signer = recover_signer(digest, signature);
+    if matches!(_signer, Ok(s) if self.is_accepted_signer(&s)) {
+        return Ok(());
+    }
+
+    let mut personal_digest: Bytes = [0u8; 32];
+    personal_digest.copy_from_slice(&eth_message_hash(digest));
+    let personal_signer = recover_signer(&personal_digest, signature);
+    if matches!(personal_signer, Ok(s) if self.is_accepted_signer(&s) && self.personal_sign_signers.contains(&s)) {
+        return Ok(());
+    }
 
-        let digest_keccak = (("WithdrawSigned(address token, address payee,uint256 amount,bytes32 salt)"),
-         token, payee, amount, salt);
+    _signer.and(Err("Bridge Pool: Invalid Signer".into()))
+}
 
-         digest = keccak256_hash(digest_keccak);
+fn is_accepted_signer(&self, signer: &SignerAddress) -> bool {
+    let is_previous = self.previous_signer.map_or(false, |(previous, _)| *signer == previous);
+    *signer == self.signer || is_previous
+}
 
-    if !self.usedHashes.get(&digest).unwrap() { 
+// Burns a withdraw_signed digest or withdrawal leaf without paying out, e.g. when a signature
+// leaked. Allowed for the signer manager calling directly, once one is set, or for anyone
+// holding a RevokeSalt signature of the signer.
+pub fn revoke_salt(
+    &mut self,
+    digest: Bytes,
+    signature: Option<&[u8]>
+) -> Result<(), String> {
+    if self.usedHashes.contains_key(&digest) {
         return Err("Message already used".into());
     }
-    let sig = EcdsaSig::from(signature).unwrap();
-    let _signer = ecdsa_recover(&digest, &sig).unwrap();  // if we can use ECDSA Openzepplin interface
-    
-    if _signer != self.signer {
-        return Err("Bridge Pool: Invalid Signer".into());
+    let is_manager = !is_zero_address(&self.signer_manager)
+        && immediate_caller() == self.signer_manager;
+    if !is_manager {
+        let signature = signature.ok_or("BridgePool: not allowed to revoke")?;
+        let revoke_digest = revoke_salt_digest(&self.domain_separator, digest);
+        self.verify_signer(&revoke_digest, signature)?;
     }
 
-    self.usedHashes.insert(digest, true);
-    // IERC20(token).safeTransfer(payee, amount);
-    // emit TransferBySignature(digest, _signer, payee, token, amount);
+    self.usedHashes.insert(digest, DigestStatus::Revoked);
+    self.events.push(BridgeEvent::SaltRevoked { digest });
+    Ok(())
+}
+
+pub fn digest_status(&self, digest: Bytes) -> Option<DigestStatus> {
+    self.usedHashes.get(&digest).copied()
+}
+
+// If the pool is temporarily short, pay what it has and queue the rest for the payee.
+fn pay_out(&mut self, token: Address, payee: Address, amount: U256) {
+    let balance = self.pool_balance(token);
+    let paid = if balance > amount { amount } else { balance };
+    if paid != ZERO {
+        self.debit_pool(token, paid);
+        // IERC20(token).safeTransfer(payee, paid);
+    }
+    if paid < amount {
+        self.enqueue_withdrawal(token, payee, amount - paid);
+    }
+}
+
+// Leaf of a withdrawal root. Leaves have their own type hash, so a root cannot re-authorize a
+// withdraw_signed signature, and are marked used in the same set once claimed.
+pub fn withdrawal_leaf(&self, token: Address, payee: Address, amount: U256, salt: Bytes) -> Bytes {
+    withdraw_leaf_digest(&self.domain_separator, token, payee, amount, salt)
+}
+
+// Registers a merkle root of `leaf_count` withdrawal leaves signed once by the signer.
+// The leaf count is signed along with the root: MerkleTree pairs the odd node of a level with
+// itself, so the last leaf also verifies at the indexes past the end.
+pub fn add_withdrawal_root(
+    &mut self,
+    root: Bytes,
+    leaf_count: u64,
+    expiry: u64,
+    signature: &[u8]
+) -> Result<(), String> {
+    if expiry <= block_time() {
+        return Err("BridgePool: root already expired".into());
+    }
+    if self.withdrawal_roots.contains_key(&root) {
+        return Err("BridgePool: root already registered".into());
+    }
+    if leaf_count == 0 {
+        return Err("BridgePool: empty root".into());
+    }
+    let digest = withdraw_root_digest(&self.domain_separator, root, leaf_count, expiry);
+    self.verify_signer(&digest, signature)?;
+
+    self.withdrawal_roots.insert(root, (expiry, leaf_count));
+    // emit WithdrawRootAdded(root, expiry);
+    Ok(())
+}
+
+pub fn withdraw_with_proof(
+    &mut self,
+    root: Bytes,
+    token: Address,
+    payee: Address,
+    amount: U256,
+    salt: Bytes,
+    leaf_index: u64,
+    proof: &[Bytes]
+) -> Result<Bytes, String> {
+    let (expiry, leaf_count) =
+        *self.withdrawal_roots.get(&root).ok_or("BridgePool: unknown root")?;
+    if block_time() >= expiry {
+        return Err("BridgePool: root expired".into());
+    }
+    if leaf_index >= leaf_count {
+        return Err("BridgePool: Invalid proof".into());
+    }
+    if self.is_leaf_claimed(root, leaf_index) {
+        return Err("Message already used".into());
+    }
+
+    let leaf = self.withdrawal_leaf(token, payee, amount, salt);
+    if self.usedHashes.contains_key(&leaf) {
+        return Err("Message already used".into());
+    }
+    if !verify_merkle_proof(&leaf, leaf_index, proof, &root) {
+        return Err("BridgePool: Invalid proof".into());
+    }
+
+    self.usedHashes.insert(leaf, DigestStatus::Executed);
+    let word = self.claimed_leaves.entry(root).or_default().entry(leaf_index / 64).or_insert(0);
+    *word |= 1 << (leaf_index % 64);
+    self.pay_out(token, payee, amount);
+    // emit TransferByProof(root, leaf, payee, token, amount);
+
+    Ok(leaf)
+}
+
+pub fn is_leaf_claimed(&self, root: Bytes, leaf_index: u64) -> bool {
+    self.claimed_leaves
+        .get(&root)
+        .and_then(|words| words.get(&(leaf_index / 64)))
+        .map_or(false, |word| word & (1 << (leaf_index % 64)) != 0)
+}
+
+// Checks every item like withdraw_signed does, then pays out the valid ones in order. With
+// `atomic` any invalid item fails the whole batch before anything is applied; otherwise
+// invalid items are reported and the rest still go through.
+// Returns the per-item digest (or error) so relayers can reconcile against their own records.
+pub fn withdraw_signed_batch(
+    &mut self,
+    withdrawals: Vec<SignedWithdrawal>,
+    atomic: bool
+) -> Result<Vec<Result<Bytes, String>>, String> {
+    self.expire_previous_signer();
+
+    let mut seen = HashSet::with_capacity(withdrawals.len());
+    let mut results = Vec::with_capacity(withdrawals.len());
+    for (i, (token, payee, amount, salt, signature)) in withdrawals.iter().enumerate() {
+        let result = self
+            .check_withdraw_signed(*token, *payee, *amount, *salt, signature)
+            .and_then(|digest| {
+                if seen.insert(digest) { Ok(digest) } else { Err("Message already used".into()) }
+            });
+        if let (true, Err(e)) = (atomic, &result) {
+            return Err(format!("BridgePool: batch item {} failed: {}", i, e));
+        }
+        results.push(result);
+    }
+
+    for ((token, payee, amount, _, _), result) in withdrawals.into_iter().zip(&results) {
+        if let Ok(digest) = result {
+            self.apply_withdraw_signed(*digest, token, payee, amount);
+        }
+    }
+
+    Ok(results)
+}
+
+}
+
+fn to_bytes32(hash: &[u8]) -> Bytes {
+    let mut bytes: Bytes = [0u8; 32];
+    bytes.copy_from_slice(hash);
+    bytes
+}
+
+// ABI encoding of a uint256.
+fn u256_word(value: U256) -> Bytes {
+    let mut word: Bytes = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+// bytes32 encoding of an account or contract package hash.
+fn address_word(address: &Address) -> Bytes {
+    match address {
+        Address::Account(account_hash) => account_hash.value(),
+        Address::Contract(contract_package_hash) => contract_package_hash.value(),
+    }
+}
+
+// _domainSeparatorV4() = keccak256(abi.encode(
+//   keccak256("EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)"),
+//      keccak256(NAME), keccak256(VERSION), chainId, pool));
+fn domain_separator(chain_id: U256, pool: Address) -> Bytes {
+    let mut encoded = keccak256_hash(EIP712_DOMAIN_TYPE.as_bytes());
+    encoded.extend(keccak256_hash(NAME.as_bytes()));
+    encoded.extend(keccak256_hash(VERSION.as_bytes()));
+    encoded.extend(&u256_word(chain_id));
+    encoded.extend(&address_word(&pool));
+    to_bytes32(&keccak256_hash(&encoded))
+}
+
+// _hashTypedDataV4(structHash) = keccak256("\x19\x01" ++ domainSeparator ++ structHash)
+fn hash_typed_data(domain_separator: &Bytes, struct_encoding: &[u8]) -> Bytes {
+    let mut encoded = Vec::with_capacity(66);
+    encoded.extend(b"\x19\x01");
+    encoded.extend(domain_separator);
+    encoded.extend(keccak256_hash(struct_encoding));
+    to_bytes32(&keccak256_hash(&encoded))
+}
+
+// digest = _hashTypedDataV4(keccak256(abi.encode(
+//   keccak256("WithdrawSigned(bytes32 token,bytes32 payee,uint256 amount,bytes32 salt)"),
+//      token, payee, amount, salt)));
+fn withdraw_signed_digest(
+    domain_separator: &Bytes,
+    token: Address,
+    payee: Address,
+    amount: U256,
+    salt: Bytes
+) -> Bytes {
+    let mut encoded = keccak256_hash(WITHDRAW_SIGNED_TYPE.as_bytes());
+    encoded.extend(&address_word(&token));
+    encoded.extend(&address_word(&payee));
+    encoded.extend(&u256_word(amount));
+    encoded.extend(&salt);
+    hash_typed_data(domain_separator, &encoded)
+}
+
+fn withdraw_leaf_digest(
+    domain_separator: &Bytes,
+    token: Address,
+    payee: Address,
+    amount: U256,
+    salt: Bytes
+) -> Bytes {
+    let mut encoded = keccak256_hash(WITHDRAW_LEAF_TYPE.as_bytes());
+    encoded.extend(&address_word(&token));
+    encoded.extend(&address_word(&payee));
+    encoded.extend(&u256_word(amount));
+    encoded.extend(&salt);
+    hash_typed_data(domain_separator, &encoded)
+}
+
+fn withdraw_root_digest(
+    domain_separator: &Bytes,
+    root: Bytes,
+    leaf_count: u64,
+    expiry: u64
+) -> Bytes {
+    let mut encoded = keccak256_hash(WITHDRAW_ROOT_TYPE.as_bytes());
+    encoded.extend(&root);
+    encoded.extend(&u256_word(U256::from(leaf_count)));
+    encoded.extend(&u256_word(U256::from(expiry)));
+    hash_typed_data(domain_separator, &encoded)
+}
+
+fn revoke_salt_digest(domain_separator: &Bytes, digest: Bytes) -> Bytes {
+    let mut encoded = keccak256_hash(REVOKE_SALT_TYPE.as_bytes());
+    encoded.extend(&digest);
+    hash_typed_data(domain_separator, &encoded)
+}
+
+fn is_zero_address(address: &Address) -> bool {
+    address_word(address) == ZERO_ADDRESS
+}
+
+// Account or contract that called the current entry point.
+#[cfg(not(test))]
+fn immediate_caller() -> Address {
+    let caller = runtime::get_call_stack().into_iter().rev().nth(1).unwrap_or_revert();
+    match caller {
+        CallStackElement::Session { account_hash } => Address::from(account_hash),
+        CallStackElement::StoredSession { account_hash, .. } => Address::from(account_hash),
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        } => Address::from(contract_package_hash),
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static CALLER: std::cell::Cell<Address> =
+        std::cell::Cell::new(Address::from(AccountHash::new(ZERO_ADDRESS)));
+}
+
+#[cfg(test)]
+fn immediate_caller() -> Address {
+    CALLER.with(|caller| caller.get())
+}
+
+// Current block time in unix seconds; expiries and overlap windows are checked against it.
+#[cfg(not(test))]
+fn block_time() -> u64 {
+    u64::from(runtime::get_blocktime()) / 1000
+}
 
-   Ok(digest)
+#[cfg(test)]
+thread_local! {
+    static BLOCK_TIME: std::cell::Cell<u64> = std::cell::Cell::new(0);
 }
 
+#[cfg(test)]
+fn block_time() -> u64 {
+    BLOCK_TIME.with(|time| time.get())
 }
 
+// Signatures are either 65 (or EIP-2098 64) byte recoverable ECDSA signatures (EVM signers), or a
+// bytesrepr encoded (PublicKey, Signature) pair for Casper Ed25519/Secp256k1 signers.
+fn recover_signer(digest: &Bytes, signature: &[u8]) -> Result<SignerAddress, String> {
+    if signature.len() == 65 || signature.len() == 64 {
+        let sig = EcdsaSig::from(signature).map_err(|_| "Bridge Pool: Bad signature")?;
+        let recovered = ecdsa_recover(digest, &sig).map_err(|_| "Bridge Pool: Bad signature")?;  // if we can use ECDSA Openzepplin interface
+        return Ok(SignerAddress::Evm(recovered));
+    }
+
+    let ((public_key, sig), remainder) = <(PublicKey, Signature)>::from_bytes(signature)
+        .map_err(|_| "Bridge Pool: Bad signature")?;
+    if !remainder.is_empty() {
+        return Err("Bridge Pool: Bad signature".into());
+    }
+    crypto::verify(digest, &sig, &public_key).map_err(|_| "Bridge Pool: Invalid Signer")?;
+    Ok(SignerAddress::Casper(AccountHash::from(&public_key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_utils::{casper_sign, private_to_address, CryptoUtils, MerkleTree};
+    use casper_types::{bytesrepr::ToBytes, ContractPackageHash, SecretKey};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const SIGNER_KEY: [u8; 32] = [0x11; 32];
+    const NOW: u64 = 1_000;
+    const EXPIRY: u64 = 2_000;
+    const AMOUNT: u64 = 100;
+
+    fn token() -> Address {
+        Address::from(ContractPackageHash::new([0xaa; 32]))
+    }
+
+    fn payee(i: u8) -> Address {
+        Address::from(AccountHash::new([i + 1; 32]))
+    }
+
+    fn salt(i: u8) -> Bytes {
+        [i; 32]
+    }
+
+    fn set_block_time(time: u64) {
+        BLOCK_TIME.with(|block_time| block_time.set(time));
+    }
+
+    fn pool() -> BridgePool {
+        set_block_time(NOW);
+        let mut pool = BridgePool::new(U256::from(1), Address::from(ContractPackageHash::new([0xbb; 32])));
+        let signer = private_to_address(&SIGNER_KEY).unwrap();
+        pool.set_signer(SignerAddress::Evm(signer)).unwrap();
+        pool
+    }
+
+    fn sign(digest: &Bytes) -> Vec<u8> {
+        CryptoUtils::new().sign(digest, &SIGNER_KEY).unwrap()
+    }
+
+    fn leaves(pool: &BridgePool, count: u8) -> Vec<Bytes> {
+        (0..count)
+            .map(|i| pool.withdrawal_leaf(token(), payee(i), U256::from(AMOUNT), salt(i)))
+            .collect()
+    }
+
+    fn add_root(pool: &mut BridgePool, tree: &MerkleTree) -> Bytes {
+        let root = tree.root();
+        let leaf_count = tree.len() as u64;
+        let digest = withdraw_root_digest(&pool.domain_separator(), root, leaf_count, EXPIRY);
+        pool.add_withdrawal_root(root, leaf_count, EXPIRY, &sign(&digest)).unwrap();
+        root
+    }
+
+    fn claim(pool: &mut BridgePool, root: Bytes, i: u8, index: u64, proof: &[Bytes]) -> Result<Bytes, String> {
+        pool.withdraw_with_proof(root, token(), payee(i), U256::from(AMOUNT), salt(i), index, proof)
+    }
+
+    fn signed_withdrawal(pool: &BridgePool, i: u8, amount: u64, salt: Bytes) -> SignedWithdrawal {
+        let amount = U256::from(amount);
+        let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(i), amount, salt);
+        (token(), payee(i), amount, salt, sign(&digest))
+    }
 
+    // The (PublicKey, Signature) pair a Casper signer submits in place of an ECDSA signature.
+    fn casper_signature(secret_key: &SecretKey, digest: &Bytes) -> Vec<u8> {
+        (PublicKey::from(secret_key), casper_sign(digest, secret_key)).to_bytes().unwrap()
+    }
 
+    fn unique_salt(n: u64) -> Bytes {
+        let mut salt = [0u8; 32];
+        salt[..8].copy_from_slice(&n.to_be_bytes());
+        salt
+    }
 
+    #[test]
+    fn last_leaf_of_odd_tree_claims_once() {
+        let mut pool = pool();
+        let tree = MerkleTree::new(leaves(&pool, 3)).unwrap();
+        let root = add_root(&mut pool, &tree);
+        let proof = tree.proof(2).unwrap();
 
+        // The last leaf is paired with itself, so its proof also verifies at the phantom index 3.
+        let leaf = pool.withdrawal_leaf(token(), payee(2), U256::from(AMOUNT), salt(2));
+        assert!(verify_merkle_proof(&leaf, 3, &proof, &root));
 
+        assert!(claim(&mut pool, root, 2, 3, &proof).is_err());
+        assert_eq!(claim(&mut pool, root, 2, 2, &proof), Ok(leaf));
+        assert!(claim(&mut pool, root, 2, 3, &proof).is_err());
+        assert!(claim(&mut pool, root, 2, 2, &proof).is_err());
+        assert_eq!(pool.pending_withdrawal(token(), payee(2)), U256::from(AMOUNT));
+    }
 
+    #[test]
+    fn leaf_claims_once_across_roots() {
+        let mut pool = pool();
+        let first = MerkleTree::new(leaves(&pool, 2)).unwrap();
+        let second = MerkleTree::new(leaves(&pool, 4)).unwrap();
+        let first_root = add_root(&mut pool, &first);
+        let second_root = add_root(&mut pool, &second);
 
+        assert!(claim(&mut pool, first_root, 0, 0, &first.proof(0).unwrap()).is_ok());
+        assert!(claim(&mut pool, second_root, 0, 0, &second.proof(0).unwrap()).is_err());
+        assert!(claim(&mut pool, second_root, 3, 3, &second.proof(3).unwrap()).is_ok());
+    }
 
+    #[test]
+    fn leaf_is_not_a_withdraw_signed_digest() {
+        let pool = pool();
+        let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(0), U256::from(AMOUNT), salt(0));
+        assert_ne!(digest, pool.withdrawal_leaf(token(), payee(0), U256::from(AMOUNT), salt(0)));
+    }
 
+    #[test]
+    fn revoked_leaf_cannot_be_claimed() {
+        let mut pool = pool();
+        let tree = MerkleTree::new(leaves(&pool, 2)).unwrap();
+        let root = add_root(&mut pool, &tree);
+        let leaf = pool.withdrawal_leaf(token(), payee(0), U256::from(AMOUNT), salt(0));
 
+        let revocation = sign(&revoke_salt_digest(&pool.domain_separator(), leaf));
+        pool.revoke_salt(leaf, Some(&revocation)).unwrap();
 
+        assert_eq!(pool.digest_status(leaf), Some(DigestStatus::Revoked));
+        assert!(claim(&mut pool, root, 0, 0, &tree.proof(0).unwrap()).is_err());
+    }
 
+    #[test]
+    fn rotation_overlap_follows_block_time() {
+        let mut pool = pool();
+        let old_signer = pool.signer;
+        let new_key = [0x22; 32];
+        let new_signer = SignerAddress::Evm(private_to_address(&new_key).unwrap());
+        pool.rotate_signer(new_signer, EXPIRY).unwrap();
+        assert!(pool.set_signer(new_signer).is_err());
 
+        let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(0), U256::from(AMOUNT), salt(0));
+        set_block_time(EXPIRY - 1);
+        assert!(pool.withdraw_signed(token(), payee(0), U256::from(AMOUNT), salt(0), &sign(&digest)).is_ok());
+
+        let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(1), U256::from(AMOUNT), salt(1));
+        set_block_time(EXPIRY);
+        assert!(pool.withdraw_signed(token(), payee(1), U256::from(AMOUNT), salt(1), &sign(&digest)).is_err());
+        assert_eq!(pool.previous_signer(), None);
+        assert_eq!(
+            pool.events().last(),
+            Some(&BridgeEvent::SignerRotationCompleted { old_signer, new_signer })
+        );
+        assert!(pool.set_signer(old_signer).is_ok());
+    }
+
+    #[test]
+    fn only_a_set_signer_manager_revokes_without_signature() {
+        let mut pool = pool();
+        let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(0), U256::from(AMOUNT), salt(0));
+        let zero = Address::from(AccountHash::new(ZERO_ADDRESS));
+
+        // No manager set: the zero address caller is not the manager.
+        CALLER.with(|caller| caller.set(zero));
+        assert!(pool.revoke_salt(digest, None).is_err());
+
+        let manager = payee(7);
+        pool.set_signer_manager(manager);
+        CALLER.with(|caller| caller.set(payee(8)));
+        assert!(pool.revoke_salt(digest, None).is_err());
+
+        CALLER.with(|caller| caller.set(manager));
+        assert!(pool.revoke_salt(digest, None).is_ok());
+        assert_eq!(pool.digest_status(digest), Some(DigestStatus::Revoked));
+    }
+
+    #[test]
+    fn casper_signers_withdraw_signed() {
+        let keys = [
+            SecretKey::ed25519_from_bytes([0x33; 32]).unwrap(),
+            SecretKey::secp256k1_from_bytes([0x44; 32]).unwrap(),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            let mut pool = pool();
+            let signer = AccountHash::from(&PublicKey::from(key));
+            pool.set_signer(SignerAddress::Casper(signer)).unwrap();
+            let other = &keys[1 - i];
+            let amount = U256::from(AMOUNT);
+            let digest = withdraw_signed_digest(&pool.domain_separator(), token(), payee(0), amount, salt(0));
+
+            // A valid signature of another account, the former EVM signer, and the signer's
+            // public key paired with another key's signature.
+            let wrong_account = casper_signature(other, &digest);
+            assert_eq!(
+                pool.withdraw_signed(token(), payee(0), amount, salt(0), &wrong_account),
+                Err("Bridge Pool: Invalid Signer".into())
+            );
+            assert!(pool.withdraw_signed(token(), payee(0), amount, salt(0), &sign(&digest)).is_err());
+            let forged = (PublicKey::from(key), casper_sign(&digest, other)).to_bytes().unwrap();
+            assert!(pool.withdraw_signed(token(), payee(0), amount, salt(0), &forged).is_err());
+
+            let signature = casper_signature(key, &digest);
+            assert_eq!(pool.withdraw_signed(token(), payee(0), amount, salt(0), &signature), Ok(digest));
+            assert_eq!(pool.digest_status(digest), Some(DigestStatus::Executed));
+            assert!(pool.withdraw_signed(token(), payee(0), amount, salt(0), &signature).is_err());
+        }
+    }
+
+    // Random sequences of deposits, LP withdrawals, signed withdrawals, batches and replays.
+    // After every step: what the pool holds minus what it still owes equals deposits minus
+    // accepted withdrawals, the queue only waits while the pool is empty, no digest is accepted
+    // twice, and an LP can take out exactly what it put in, but no more.
+    #[test]
+    fn random_operations_keep_the_pool_consistent() {
+        const ACCOUNTS: u8 = 6;
+
+        for seed in 1..=16u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut pool = pool();
+            let mut net: i128 = 0;
+            let mut credits = [None::<u64>; ACCOUNTS as usize];
+            let mut accepted: Vec<SignedWithdrawal> = Vec::new();
+            let mut digests = HashSet::new();
+            let mut salts = 0u64;
+
+            for step in 0..150 {
+                let account = rng.gen_range(0..ACCOUNTS);
+                let amount = rng.gen_range(1..=1_000u64);
+                match rng.gen_range(0..5) {
+                    0 => {
+                        pool.add_liquidity(payee(account), token(), U256::from(amount)).unwrap();
+                        let credit = credits[account as usize].get_or_insert(0);
+                        *credit += amount;
+                        net += amount as i128;
+                    }
+                    1 => {
+                        // Half of the LP withdrawals take out the whole position.
+                        let amount = match credits[account as usize] {
+                            Some(credit) if credit > 0 && rng.gen_bool(0.5) => credit,
+                            _ => amount,
+                        };
+                        let result = pool.remove_liquidity_ifpossible(payee(account), token(), U256::from(amount));
+                        match credits[account as usize].as_mut() {
+                            Some(credit) if amount <= *credit => {
+                                assert!(result.is_ok(), "seed {} step {}: {:?}", seed, step, result);
+                                *credit -= amount;
+                                net -= amount as i128;
+                            }
+                            _ => assert!(result.is_err(), "seed {} step {}: LP overdraw accepted", seed, step),
+                        }
+                    }
+                    2 => {
+                        salts += 1;
+                        let (token, payee, amount, salt, signature) =
+                            signed_withdrawal(&pool, account, amount, unique_salt(salts));
+                        let digest = pool.withdraw_signed(token, payee, amount, salt, &signature).unwrap();
+                        assert!(digests.insert(digest), "seed {} step {}: digest accepted twice", seed, step);
+                        net -= amount.as_u64() as i128;
+                        accepted.push((token, payee, amount, salt, signature));
+                    }
+                    3 if !accepted.is_empty() => {
+                        let (token, payee, amount, salt, signature) =
+                            accepted[rng.gen_range(0..accepted.len())].clone();
+                        assert!(
+                            pool.withdraw_signed(token, payee, amount, salt, &signature).is_err(),
+                            "seed {} step {}: replay accepted",
+                            seed,
+                            step
+                        );
+                    }
+                    _ => {
+                        salts += 1;
+                        let fresh = signed_withdrawal(&pool, account, amount, unique_salt(salts));
+                        let mut batch = vec![fresh.clone(), fresh.clone()];
+                        if !accepted.is_empty() {
+                            batch.push(accepted[rng.gen_range(0..accepted.len())].clone());
+                        }
+                        assert!(pool.withdraw_signed_batch(batch.clone(), true).is_err());
+
+                        let results = pool.withdraw_signed_batch(batch, false).unwrap();
+                        let digest = *results[0].as_ref().unwrap();
+                        assert!(results[1..].iter().all(|result| result.is_err()));
+                        assert!(digests.insert(digest), "seed {} step {}: digest accepted twice", seed, step);
+                        net -= amount as i128;
+                        accepted.push(fresh);
+                    }
+                }
+
+                let balance = pool.pool_balance(token()).as_u64() as i128;
+                let pending: i128 = (0..ACCOUNTS)
+                    .map(|i| pool.pending_withdrawal(token(), payee(i)).as_u64() as i128)
+                    .sum();
+                assert_eq!(balance - pending, net, "seed {} step {}: funds not conserved", seed, step);
+                assert!(pending == 0 || balance == 0, "seed {} step {}: queue left unserved", seed, step);
+            }
+
+            for (i, credit) in credits.iter().enumerate() {
+                if let Some(credit) = credit {
+                    assert_eq!(*pool.liquidity(token(), payee(i as u8)), U256::from(*credit));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Named key, dictionary and call stack helpers.
+use alloc::string::String;
+use core::convert::TryInto;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_erc20::Address;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    system::CallStackElement,
+    ApiError, CLTyped, URef,
+};
+
+use crate::error::Error;
+
+/// Address of the zero account, the equivalent of Solidity's `address(0)`.
+pub const ZERO_ADDRESS: Address = Address::Account(AccountHash::new([0u8; 32]));
+
+/// Gets [`URef`] under a name.
+pub fn get_uref(name: &str) -> URef {
+    let key = runtime::get_key(name)
+        .ok_or(ApiError::MissingKey)
+        .unwrap_or_revert();
+    key.try_into().unwrap_or_revert()
+}
+
+/// Reads value from a named key.
+pub fn read_from<T>(name: &str) -> T
+where
+    T: FromBytes + CLTyped,
+{
+    let uref = get_uref(name);
+    let value: T = storage::read(uref).unwrap_or_revert().unwrap_or_revert();
+    value
+}
+
+/// Writes value to a named key.
+pub fn write_to<T>(name: &str, value: T)
+where
+    T: ToBytes + CLTyped,
+{
+    let uref = get_uref(name);
+    storage::write(uref, value);
+}
+
+/// Creates a dictionary item key for an address.
+///
+/// Same encoding as the ERC20 balances dictionary: base64 of the serialized address fits the 64
+/// character limit of dictionary item keys without hashing.
+pub fn make_dictionary_item_key(address: Address) -> String {
+    let preimage = address.to_bytes().unwrap_or_revert();
+    base64::encode(&preimage)
+}
+
+/// Reads a value stored for `address` in a dictionary.
+pub fn read_dictionary<T>(dictionary_uref: URef, address: Address) -> Option<T>
+where
+    T: FromBytes + CLTyped,
+{
+    let dictionary_item_key = make_dictionary_item_key(address);
+    storage::dictionary_get(dictionary_uref, &dictionary_item_key).unwrap_or_revert()
+}
+
+/// Writes a value for `address` into a dictionary.
+pub fn write_dictionary<T>(dictionary_uref: URef, address: Address, value: T)
+where
+    T: ToBytes + CLTyped,
+{
+    let dictionary_item_key = make_dictionary_item_key(address);
+    storage::dictionary_put(dictionary_uref, &dictionary_item_key, value);
+}
+
+/// Creates a dictionary item key for a pair of addresses.
+///
+/// Two serialized addresses are too long for a dictionary item key, so the pair is hashed first,
+/// like the ERC20 allowances dictionary does.
+pub fn make_pair_dictionary_item_key(first: Address, second: Address) -> String {
+    let mut preimage = first.to_bytes().unwrap_or_revert();
+    preimage.append(&mut second.to_bytes().unwrap_or_revert());
+    base64::encode(&runtime::blake2b(&preimage))
+}
+
+/// Reads a value stored for a pair of addresses in a dictionary.
+pub fn read_pair_dictionary<T>(
+    dictionary_uref: URef,
+    first: Address,
+    second: Address,
+) -> Option<T>
+where
+    T: FromBytes + CLTyped,
+{
+    let dictionary_item_key = make_pair_dictionary_item_key(first, second);
+    storage::dictionary_get(dictionary_uref, &dictionary_item_key).unwrap_or_revert()
+}
+
+/// Writes a value for a pair of addresses into a dictionary.
+pub fn write_pair_dictionary<T>(
+    dictionary_uref: URef,
+    first: Address,
+    second: Address,
+    value: T,
+) where
+    T: ToBytes + CLTyped,
+{
+    let dictionary_item_key = make_pair_dictionary_item_key(first, second);
+    storage::dictionary_put(dictionary_uref, &dictionary_item_key, value);
+}
+
+/// Gets the immediate call stack element of the current execution.
+fn get_immediate_call_stack_item() -> Option<CallStackElement> {
+    let call_stack = runtime::get_call_stack();
+    call_stack.into_iter().rev().nth(1)
+}
+
+/// Returns address based on a [`CallStackElement`].
+fn call_stack_element_to_address(call_stack_element: CallStackElement) -> Address {
+    match call_stack_element {
+        CallStackElement::Session { account_hash } => Address::from(account_hash),
+        CallStackElement::StoredSession { account_hash, .. } => Address::from(account_hash),
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        } => Address::from(contract_package_hash),
+    }
+}
+
+/// Gets the immediate caller of the current execution.
+pub fn get_immediate_caller_address() -> Result<Address, Error> {
+    get_immediate_call_stack_item()
+        .map(call_stack_element_to_address)
+        .ok_or(Error::InvalidContext)
+}
+
+/// Gets the address stored on the top of the call stack.
+///
+/// Called from an entry point this is the executing contract itself, and during install it is
+/// the installing account.
+pub fn get_caller_address() -> Result<Address, Error> {
+    let call_stack = runtime::get_call_stack();
+    let top_of_the_stack = call_stack
+        .into_iter()
+        .rev()
+        .next()
+        .ok_or(Error::InvalidContext)?;
+    let address = call_stack_element_to_address(top_of_the_stack);
+    Ok(address)
+}
@@ -0,0 +1,16 @@
+//! Errors of the shared helpers.
+
+/// Errors which can be returned by the shared helpers.
+///
+/// Contracts convert these into their own error type, which in turn becomes an
+/// [`ApiError::User`](casper_types::ApiError::User).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Error {
+    /// Called from within an invalid context.
+    InvalidContext,
+    /// Token address is not a contract package.
+    BadToken,
+}
+
+/// User error code of `InvalidContext`, the same in every contract using these helpers.
+pub(crate) const ERROR_INVALID_CONTEXT: u16 = 1;
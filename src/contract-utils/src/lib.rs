@@ -0,0 +1,17 @@
+//! Helpers shared by the tax distributor, staking pool, reward router and taxed ERC20 contracts.
+//!
+//! [`detail`] wraps named keys, dictionaries and the call stack the way casper-erc20 does, and
+//! [`Token`] calls another casper-erc20 token contract. Each contract maps [`Error`] into its own
+//! error type, so the user error codes of every contract stay as they are.
+
+#![warn(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+pub mod detail;
+mod error;
+pub mod token;
+
+pub use error::Error;
+pub use token::Token;
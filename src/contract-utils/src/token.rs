@@ -0,0 +1,97 @@
+//! Cross-contract reference to a casper-erc20 token.
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, AMOUNT_RUNTIME_ARG_NAME, BALANCE_OF_ENTRY_POINT_NAME,
+        OWNER_RUNTIME_ARG_NAME, RECIPIENT_RUNTIME_ARG_NAME, TRANSFER_ENTRY_POINT_NAME,
+        TRANSFER_FROM_ENTRY_POINT_NAME,
+    },
+    Address,
+};
+use casper_types::{runtime_args, ApiError, ContractPackageHash, RuntimeArgs, U256};
+
+use crate::{
+    detail,
+    error::{Error, ERROR_INVALID_CONTEXT},
+};
+
+/// Name of the `burn(amount)` entry point of burnable tokens.
+pub const BURN_ENTRY_POINT_NAME: &str = "burn";
+
+/// ERC20 token contract package, called through the standard entry points and, for burnable
+/// tokens, `burn(amount)`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Token(ContractPackageHash);
+
+impl Token {
+    /// Returns the token installed as the contract package at `address`.
+    pub fn from_address(address: Address) -> Result<Self, Error> {
+        address
+            .as_contract_package_hash()
+            .map(|contract_package_hash| Token(*contract_package_hash))
+            .ok_or(Error::BadToken)
+    }
+
+    /// Returns the address of the token contract package.
+    pub fn address(&self) -> Address {
+        Address::from(self.0)
+    }
+
+    /// Returns the token balance of `owner`.
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            BALANCE_OF_ENTRY_POINT_NAME,
+            runtime_args! {
+                ADDRESS_RUNTIME_ARG_NAME => owner,
+            },
+        )
+    }
+
+    /// Transfers `amount` of the caller's tokens to `recipient`.
+    pub fn transfer(&self, recipient: Address, amount: U256) {
+        runtime::call_versioned_contract::<()>(
+            self.0,
+            None,
+            TRANSFER_ENTRY_POINT_NAME,
+            runtime_args! {
+                RECIPIENT_RUNTIME_ARG_NAME => recipient,
+                AMOUNT_RUNTIME_ARG_NAME => amount,
+            },
+        )
+    }
+
+    /// Transfers `amount` of `owner`'s tokens to `recipient`, spending the caller's allowance.
+    pub fn transfer_from(&self, owner: Address, recipient: Address, amount: U256) {
+        runtime::call_versioned_contract::<()>(
+            self.0,
+            None,
+            TRANSFER_FROM_ENTRY_POINT_NAME,
+            runtime_args! {
+                OWNER_RUNTIME_ARG_NAME => owner,
+                RECIPIENT_RUNTIME_ARG_NAME => recipient,
+                AMOUNT_RUNTIME_ARG_NAME => amount,
+            },
+        )
+    }
+
+    /// Burns `amount` of the caller's tokens. Reverts if the token has no `burn` entry point.
+    pub fn burn(&self, amount: U256) {
+        runtime::call_versioned_contract::<()>(
+            self.0,
+            None,
+            BURN_ENTRY_POINT_NAME,
+            runtime_args! {
+                AMOUNT_RUNTIME_ARG_NAME => amount,
+            },
+        )
+    }
+}
+
+/// Returns the address of the currently executing contract.
+pub fn self_address() -> Address {
+    detail::get_caller_address()
+        .map_err(|_| ApiError::User(ERROR_INVALID_CONTEXT))
+        .unwrap_or_revert()
+}
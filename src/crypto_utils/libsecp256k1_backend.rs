@@ -0,0 +1,52 @@
+//! Pure-Rust secp256k1 (the `libsecp256k1` crate). Builds for `wasm32-unknown-unknown` and
+//! without `std`, so it is the one to use for on-chain signature checks.
+use core::convert::TryInto;
+
+use libsecp256k1::{Message, PublicKey, RecoveryId, SecretKey, Signature};
+
+use super::CryptoError;
+
+fn message(hash: &[u8]) -> Result<Message, CryptoError> {
+    let hash: [u8; 32] = hash.try_into().map_err(|_| CryptoError::BadLength {
+        expected: 32,
+        actual: hash.len(),
+    })?;
+    Ok(Message::parse(&hash))
+}
+
+fn secret_key(private_key: &[u8]) -> Result<SecretKey, CryptoError> {
+    SecretKey::parse_slice(private_key).map_err(|_| CryptoError::InvalidKey)
+}
+
+pub fn public_key_uncompressed(private_key: &[u8]) -> Result<[u8; 65], CryptoError> {
+    let key = secret_key(private_key)?;
+    Ok(PublicKey::from_secret_key(&key).serialize())
+}
+
+pub fn public_key_compressed(private_key: &[u8]) -> Result<[u8; 33], CryptoError> {
+    let key = secret_key(private_key)?;
+    Ok(PublicKey::from_secret_key(&key).serialize_compressed())
+}
+
+/// `(private_key + tweak) mod n`, as used by BIP-32 child key derivation.
+pub fn tweak_add_private(private_key: &[u8], tweak: &[u8]) -> Result<[u8; 32], CryptoError> {
+    let mut key = secret_key(private_key)?;
+    key.tweak_add_assign(&secret_key(tweak)?)
+        .map_err(|_| CryptoError::InvalidKey)?;
+    Ok(key.serialize())
+}
+
+pub fn sign_recoverable(hash: &[u8], private_key: &[u8]) -> Result<(u8, [u8; 64]), CryptoError> {
+    let msg = message(hash)?;
+    let key = secret_key(private_key)?;
+    let (sig, rec_id) = libsecp256k1::sign(&msg, &key);
+    Ok((rec_id.serialize(), sig.serialize()))
+}
+
+pub fn recover_uncompressed(hash: &[u8], rs: &[u8], v: u8) -> Result<[u8; 65], CryptoError> {
+    let msg = message(hash)?;
+    let rec_id = RecoveryId::parse(v).map_err(|_| CryptoError::BadRecoveryId)?;
+    let sig = Signature::parse_standard_slice(rs).map_err(|_| CryptoError::InvalidSignature)?;
+    let pub_key = libsecp256k1::recover(&msg, &sig, &rec_id).map_err(|_| CryptoError::RecoveryFailed)?;
+    Ok(pub_key.serialize())
+}
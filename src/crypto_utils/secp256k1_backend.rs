@@ -0,0 +1,57 @@
+//! C-backed secp256k1 (libsecp256k1 bindings). Default for host-side tooling.
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{key::SecretKey, Message, PublicKey, Secp256k1};
+
+use super::CryptoError;
+
+fn message(hash: &[u8]) -> Result<Message, CryptoError> {
+    Message::from_slice(hash).map_err(|_| CryptoError::BadLength {
+        expected: 32,
+        actual: hash.len(),
+    })
+}
+
+fn secret_key(private_key: &[u8]) -> Result<SecretKey, CryptoError> {
+    SecretKey::from_slice(private_key).map_err(|_| CryptoError::InvalidKey)
+}
+
+pub fn public_key_uncompressed(private_key: &[u8]) -> Result<[u8; 65], CryptoError> {
+    let s = Secp256k1::signing_only();
+    let key = secret_key(private_key)?;
+    Ok(PublicKey::from_secret_key(&s, &key).serialize_uncompressed())
+}
+
+pub fn public_key_compressed(private_key: &[u8]) -> Result<[u8; 33], CryptoError> {
+    let s = Secp256k1::signing_only();
+    let key = secret_key(private_key)?;
+    Ok(PublicKey::from_secret_key(&s, &key).serialize())
+}
+
+/// `(private_key + tweak) mod n`, as used by BIP-32 child key derivation.
+pub fn tweak_add_private(private_key: &[u8], tweak: &[u8]) -> Result<[u8; 32], CryptoError> {
+    let mut key = secret_key(private_key)?;
+    key.add_assign(tweak).map_err(|_| CryptoError::InvalidKey)?;
+    let mut tweaked = [0u8; 32];
+    tweaked.copy_from_slice(&key[..]);
+    Ok(tweaked)
+}
+
+pub fn sign_recoverable(hash: &[u8], private_key: &[u8]) -> Result<(u8, [u8; 64]), CryptoError> {
+    let s = Secp256k1::signing_only();
+    let msg = message(hash)?;
+    let key = secret_key(private_key)?;
+    let (v, sig_bytes) = s.sign_recoverable(&msg, &key).serialize_compact();
+    Ok((v.to_i32() as u8, sig_bytes))
+}
+
+pub fn recover_uncompressed(hash: &[u8], rs: &[u8], v: u8) -> Result<[u8; 65], CryptoError> {
+    let s = Secp256k1::verification_only();
+    let msg = message(hash)?;
+    let rec_id = RecoveryId::from_i32(v as i32).map_err(|_| CryptoError::BadRecoveryId)?;
+    let rec_sig =
+        RecoverableSignature::from_compact(rs, rec_id).map_err(|_| CryptoError::InvalidSignature)?;
+    let pub_key = s
+        .recover(&msg, &rec_sig)
+        .map_err(|_| CryptoError::RecoveryFailed)?;
+    Ok(pub_key.serialize_uncompressed())
+}
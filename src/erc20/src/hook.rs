@@ -0,0 +1,36 @@
+//! Extension point for token transfers.
+use casper_types::U256;
+
+use crate::{error::Error, Address};
+
+/// Hook run by [`ERC20::transfer_with_hook`](crate::ERC20::transfer_with_hook) and
+/// [`ERC20::transfer_from_with_hook`](crate::ERC20::transfer_from_with_hook).
+///
+/// Lets a token built on this library take a fee from transfers, or act after them, without
+/// reimplementing balance handling. The provided methods do nothing.
+pub trait TransferHook {
+    /// Returns the address credited with a fee taken from a transfer of `amount` from `sender` to
+    /// `recipient`, and the fee. The recipient is credited with `amount` minus the fee.
+    fn transfer_fee(
+        &mut self,
+        _sender: Address,
+        _recipient: Address,
+        _amount: U256,
+    ) -> Result<Option<(Address, U256)>, Error> {
+        Ok(None)
+    }
+
+    /// Called once `amount` has left `sender`'s balance, `fee` of it going to the fee address.
+    fn after_transfer(
+        &mut self,
+        _sender: Address,
+        _recipient: Address,
+        _amount: U256,
+        _fee: U256,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Hook of plain transfers.
+impl TransferHook for () {}
@@ -22,6 +22,7 @@ pub mod constants;
 mod detail;
 pub mod entry_points;
 mod error;
+mod hook;
 mod total_supply;
 
 use alloc::string::{String, ToString};
@@ -40,6 +41,7 @@ use constants::{
     NAME_KEY_NAME, SYMBOL_KEY_NAME, TOTAL_SUPPLY_KEY_NAME,
 };
 pub use error::Error;
+pub use hook::TransferHook;
 
 /// Implementation of ERC20 standard functionality.
 #[derive(Default)]
@@ -107,6 +109,31 @@ impl ERC20 {
         balances::transfer_balance(self.balances_uref(), sender, recipient, amount)
     }
 
+    fn transfer_balance_with_hook<H: TransferHook>(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+        hook: &mut H,
+    ) -> Result<(), Error> {
+        if sender == recipient || amount.is_zero() {
+            return Ok(());
+        }
+        let fee = match hook.transfer_fee(sender, recipient, amount)? {
+            Some((fee_recipient, fee)) if !fee.is_zero() => {
+                let net_amount = amount.checked_sub(fee).ok_or(Error::Overflow)?;
+                self.transfer_balance(sender, fee_recipient, fee)?;
+                self.transfer_balance(sender, recipient, net_amount)?;
+                fee
+            }
+            _ => {
+                self.transfer_balance(sender, recipient, amount)?;
+                U256::zero()
+            }
+        };
+        hook.after_transfer(sender, recipient, amount, fee)
+    }
+
     /// Installs the ERC20 contract with the default set of entry points.
     ///
     /// This should be called from within `fn call()` of your contract.
@@ -154,8 +181,19 @@ impl ERC20 {
 
     /// Transfers `amount` of tokens from the direct caller to `recipient`.
     pub fn transfer(&mut self, recipient: Address, amount: U256) -> Result<(), Error> {
+        self.transfer_with_hook(recipient, amount, &mut ())
+    }
+
+    /// Transfers `amount` of tokens from the direct caller to `recipient`, running `hook` on the
+    /// transfer.
+    pub fn transfer_with_hook<H: TransferHook>(
+        &mut self,
+        recipient: Address,
+        amount: U256,
+        hook: &mut H,
+    ) -> Result<(), Error> {
         let sender = detail::get_immediate_caller_address()?;
-        self.transfer_balance(sender, recipient, amount)
+        self.transfer_balance_with_hook(sender, recipient, amount, hook)
     }
 
     /// Transfers `amount` of tokens from `owner` to `recipient` if the direct caller has been
@@ -165,6 +203,18 @@ impl ERC20 {
         owner: Address,
         recipient: Address,
         amount: U256,
+    ) -> Result<(), Error> {
+        self.transfer_from_with_hook(owner, recipient, amount, &mut ())
+    }
+
+    /// Like [`ERC20::transfer_from`], running `hook` on the transfer. The whole `amount`, fee
+    /// included, is spent from the allowance.
+    pub fn transfer_from_with_hook<H: TransferHook>(
+        &mut self,
+        owner: Address,
+        recipient: Address,
+        amount: U256,
+        hook: &mut H,
     ) -> Result<(), Error> {
         let spender = detail::get_immediate_caller_address()?;
         if amount.is_zero() {
@@ -174,9 +224,9 @@ impl ERC20 {
         let new_spender_allowance = spender_allowance
             .checked_sub(amount)
             .ok_or(Error::InsufficientAllowance)?;
-        self.transfer_balance(owner, recipient, amount)?;
+        // The allowance is spent before the hook can call out to other contracts.
         self.write_allowance(owner, spender, new_spender_allowance);
-        Ok(())
+        self.transfer_balance_with_hook(owner, recipient, amount, hook)
     }
 
     /// Allows `spender` to transfer up to `amount` of the direct caller's tokens.
@@ -249,14 +299,39 @@ impl ERC20 {
         initial_supply: U256,
         contract_key_name: &str,
         entry_points: EntryPoints,
+    ) -> Result<ERC20, Error> {
+        ERC20::install_custom_with_named_keys(
+            name,
+            symbol,
+            decimals,
+            initial_supply,
+            contract_key_name,
+            entry_points,
+            NamedKeys::new(),
+        )
+    }
+
+    /// Installs the ERC20 contract with a custom set of entry points and additional named keys,
+    /// for tokens extending this library with their own state.
+    ///
+    /// # Warning
+    ///
+    /// The same warning as for [`ERC20::install_custom`] applies.
+    #[doc(hidden)]
+    pub fn install_custom_with_named_keys(
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_supply: U256,
+        contract_key_name: &str,
+        entry_points: EntryPoints,
+        mut named_keys: NamedKeys,
     ) -> Result<ERC20, Error> {
         let balances_uref = storage::new_dictionary(BALANCES_KEY_NAME).unwrap_or_revert();
         let allowances_uref = storage::new_dictionary(ALLOWANCES_KEY_NAME).unwrap_or_revert();
         // We need to hold on a RW access rights because tokens can be minted or burned.
         let total_supply_uref = storage::new_uref(initial_supply).into_read_write();
 
-        let mut named_keys = NamedKeys::new();
-
         let name_key = {
             let name_uref = storage::new_uref(name).into_read();
             Key::from(name_uref)
@@ -0,0 +1,334 @@
+//! BIP-39 mnemonics and BIP-32/BIP-44 key derivation for signer and test keys.
+//!
+//! Derived keys are raw 32 byte secp256k1 private keys, so they can be passed straight to
+//! `CryptoUtils::sign` and `private_to_address`, or turned into a Casper `SecretKey`.
+use std::fmt;
+use std::str::FromStr;
+
+use bip39::Mnemonic;
+use casper_types::SecretKey;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+use crate::crypto_utils::{public_key_compressed, tweak_add_private, CryptoError};
+
+type HmacSha512 = Hmac<Sha512>;
+
+const HARDENED: u32 = 0x8000_0000;
+/// SLIP-44 coin type of Ethereum.
+pub const ETHEREUM_COIN_TYPE: u32 = 60;
+/// SLIP-44 coin type of Casper.
+pub const CASPER_COIN_TYPE: u32 = 506;
+
+/// Seed of a BIP-39 mnemonic. The phrase checksum is validated; `passphrase` may be empty.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], CryptoError> {
+    let mnemonic = Mnemonic::parse_normalized(phrase).map_err(|_| CryptoError::InvalidKey)?;
+    Ok(mnemonic.to_seed_normalized(passphrase))
+}
+
+/// BIP-32 path such as `m/44'/60'/0'/0/0`. Hardened components are stored with the top bit set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// `m/44'/coin_type'/account'/0/index`
+    pub fn bip44(coin_type: u32, account: u32, index: u32) -> Self {
+        DerivationPath(vec![
+            44 | HARDENED,
+            coin_type | HARDENED,
+            account | HARDENED,
+            0,
+            index,
+        ])
+    }
+
+    pub fn ethereum(account: u32, index: u32) -> Self {
+        Self::bip44(ETHEREUM_COIN_TYPE, account, index)
+    }
+
+    pub fn casper(account: u32, index: u32) -> Self {
+        Self::bip44(CASPER_COIN_TYPE, account, index)
+    }
+
+    pub fn components(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(CryptoError::InvalidPath);
+        }
+        parts
+            .map(|part| {
+                let (digits, hardened) = match part.strip_suffix('\'').or_else(|| part.strip_suffix('h')) {
+                    Some(digits) => (digits, true),
+                    None => (part, false),
+                };
+                let index: u32 = digits.parse().map_err(|_| CryptoError::InvalidPath)?;
+                if index >= HARDENED {
+                    return Err(CryptoError::InvalidPath);
+                }
+                Ok(if hardened { index | HARDENED } else { index })
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if index & HARDENED != 0 {
+                write!(f, "/{}'", index & !HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// BIP-32 extended private key.
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    private_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ExtendedPrivateKey {{ .. }}")
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(data);
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+impl ExtendedPrivateKey {
+    pub fn from_seed(seed: &[u8]) -> Result<Self, CryptoError> {
+        let i = hmac_sha512(b"Bitcoin seed", seed);
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&i[..32]);
+        // rejects a zero or out of range master key
+        public_key_compressed(&private_key)?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code,
+        })
+    }
+
+    pub fn child(&self, index: u32) -> Result<Self, CryptoError> {
+        let mut data = Vec::with_capacity(37);
+        if index & HARDENED != 0 {
+            data.push(0);
+            data.extend_from_slice(&self.private_key);
+        } else {
+            data.extend_from_slice(&public_key_compressed(&self.private_key)?);
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let i = hmac_sha512(&self.chain_code, &data);
+        let private_key = tweak_add_private(&self.private_key, &i[..32])?;
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&i[32..]);
+        Ok(ExtendedPrivateKey {
+            private_key,
+            chain_code,
+        })
+    }
+
+    pub fn derive(&self, path: &DerivationPath) -> Result<Self, CryptoError> {
+        path.components()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.child(*index))
+    }
+
+    pub fn private_key(&self) -> &[u8; 32] {
+        &self.private_key
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+}
+
+/// Raw private key at `path` below the mnemonic's master key.
+pub fn derive_private_key(
+    phrase: &str,
+    passphrase: &str,
+    path: &DerivationPath,
+) -> Result<[u8; 32], CryptoError> {
+    let seed = mnemonic_to_seed(phrase, passphrase)?;
+    let key = ExtendedPrivateKey::from_seed(&seed)?.derive(path)?;
+    Ok(*key.private_key())
+}
+
+/// Ethereum signer key `m/44'/60'/account'/0/index`.
+pub fn derive_ethereum_key(
+    phrase: &str,
+    passphrase: &str,
+    account: u32,
+    index: u32,
+) -> Result<[u8; 32], CryptoError> {
+    derive_private_key(phrase, passphrase, &DerivationPath::ethereum(account, index))
+}
+
+/// Casper secp256k1 key `m/44'/506'/account'/0/index`.
+pub fn derive_casper_key(
+    phrase: &str,
+    passphrase: &str,
+    account: u32,
+    index: u32,
+) -> Result<SecretKey, CryptoError> {
+    let private_key = derive_private_key(phrase, passphrase, &DerivationPath::casper(account, index))?;
+    SecretKey::secp256k1_from_bytes(private_key).map_err(|_| CryptoError::InvalidKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto_utils::{b2h, h2b, private_to_address, EthAddress};
+
+    const ABANDON: &str = "abandon abandon abandon abandon abandon abandon \
+                           abandon abandon abandon abandon abandon about";
+
+    // BIP-32 test vector 1: (path, chain code, private key).
+    const TV1_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+    const TV1: [(&str, &str, &str); 6] = [
+        (
+            "m",
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+        ),
+        (
+            "m/0'",
+            "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        ),
+        (
+            "m/0'/1",
+            "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        (
+            "m/0'/1/2'",
+            "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        ),
+        (
+            "m/0'/1/2'/2",
+            "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+        ),
+        (
+            "m/0'/1/2'/2/1000000000",
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ),
+    ];
+
+    // BIP-32 test vector 2.
+    const TV2_SEED: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a2\
+                            9f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+    const TV2: [(&str, &str, &str); 6] = [
+        (
+            "m",
+            "60499f801b896d83179a4374aeb7822aaeaceaa0db1f85ee3e904c4defbd9689",
+            "4b03d6fc340455b363f51020ad3ecca4f0850280cf436c70c727923f6db46c3e",
+        ),
+        (
+            "m/0",
+            "f0909affaa7ee7abe5dd4e100598d4dc53cd709d5a5c2cac40e7412f232f7c9c",
+            "abe74a98f6c7eabee0428f53798f0ab8aa1bd37873999041703c742f15ac7e1e",
+        ),
+        (
+            "m/0/2147483647'",
+            "be17a268474a6bb9c61e1d720cf6215e2a88c5406c4aee7b38547f585c9a37d9",
+            "877c779ad9687164e9c2f4f0f4ff0340814392330693ce95a58fe18fd52e6e93",
+        ),
+        (
+            "m/0/2147483647'/1",
+            "f366f48f1ea9f2d1d3fe958c95ca84ea18e4c4ddb9366c336c927eb246fb38cb",
+            "704addf544a06e5ee4bea37098463c23613da32020d604506da8c0518e1da4b7",
+        ),
+        (
+            "m/0/2147483647'/1/2147483646'",
+            "637807030d55d01f9a0cb3a7839515d796bd07706386a6eddf06cc29a65a0e29",
+            "f1c7c871a54a804afe328b4c83a1c33b8e5ff48f5087273f04efa83b247d6a2d",
+        ),
+        (
+            "m/0/2147483647'/1/2147483646'/2",
+            "9452b549be8cea3ecb7a84bec10dcfd94afe4d129ebfd3b3cb58eedf394ed271",
+            "bb7d39bdb83ecf58f2fd82b6d918341cbef428661ef01ab97c28a4842125ac23",
+        ),
+    ];
+
+    fn check_vector(seed: &str, vector: &[(&str, &str, &str)]) {
+        let master = ExtendedPrivateKey::from_seed(&h2b(seed).unwrap()).unwrap();
+        for (path, chain_code, private_key) in vector {
+            let path: DerivationPath = path.parse().unwrap();
+            let key = master.derive(&path).unwrap();
+            assert_eq!(b2h(key.chain_code()), *chain_code, "chain code at {}", path);
+            assert_eq!(b2h(key.private_key()), *private_key, "private key at {}", path);
+        }
+    }
+
+    #[test]
+    fn bip32_test_vector_1() {
+        check_vector(TV1_SEED, &TV1);
+    }
+
+    #[test]
+    fn bip32_test_vector_2() {
+        check_vector(TV2_SEED, &TV2);
+    }
+
+    #[test]
+    fn bip39_seed_matches_reference_vector() {
+        let seed = mnemonic_to_seed(ABANDON, "TREZOR").unwrap();
+        assert_eq!(
+            b2h(&seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553\
+             1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    fn bip39_rejects_a_bad_checksum() {
+        let phrase = ABANDON.replace("about", "abandon");
+        assert!(mnemonic_to_seed(&phrase, "").is_err());
+    }
+
+    #[test]
+    fn bip44_ethereum_key_matches_reference_wallets() {
+        let key = derive_ethereum_key(ABANDON, "", 0, 0).unwrap();
+        assert_eq!(
+            b2h(&key),
+            "1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727"
+        );
+        let address: EthAddress = "0x9858EfFD232B4033E47d90003D41EC34EcaEda94".parse().unwrap();
+        assert_eq!(private_to_address(&key).unwrap(), address);
+    }
+
+    #[test]
+    fn derivation_path_round_trips() {
+        let path: DerivationPath = "m/44'/60'/0'/0/7".parse().unwrap();
+        assert_eq!(path, DerivationPath::ethereum(0, 7));
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/7");
+        assert!("44'/60'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+    }
+}
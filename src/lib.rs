@@ -1,5 +1,12 @@
 #![allow(dead_code)]
+extern crate alloc;
+
 pub mod crypto_utils;
+#[cfg(feature = "hd-keys")]
+pub mod hd_keys;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+#[cfg(feature = "signer")]
+pub mod signer;
 mod bridge_pool;
 mod safe_amount;
-mod tax_distributor;
@@ -0,0 +1,101 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20::Address;
+use casper_reward_router::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, POOL_RUNTIME_ARG_NAME, SENDER_RUNTIME_ARG_NAME,
+        TAX_DISTRIBUTOR_RUNTIME_ARG_NAME, WEIGHT_RUNTIME_ARG_NAME,
+    },
+    RewardRouter,
+};
+use casper_types::CLValue;
+
+#[no_mangle]
+pub extern "C" fn add_pool() {
+    let pool: Address = runtime::get_named_arg(POOL_RUNTIME_ARG_NAME);
+    let weight: u32 = runtime::get_named_arg(WEIGHT_RUNTIME_ARG_NAME);
+
+    RewardRouter::default()
+        .add_pool(pool, weight)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn remove_pool() {
+    let pool: Address = runtime::get_named_arg(POOL_RUNTIME_ARG_NAME);
+
+    RewardRouter::default().remove_pool(pool).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_pool_weight() {
+    let pool: Address = runtime::get_named_arg(POOL_RUNTIME_ARG_NAME);
+    let weight: u32 = runtime::get_named_arg(WEIGHT_RUNTIME_ARG_NAME);
+
+    RewardRouter::default()
+        .set_pool_weight(pool, weight)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_tax_distributor() {
+    let tax_distributor: Address = runtime::get_named_arg(TAX_DISTRIBUTOR_RUNTIME_ARG_NAME);
+
+    RewardRouter::default()
+        .set_tax_distributor(tax_distributor)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn tax_distributor() {
+    let tax_distributor = RewardRouter::default().tax_distributor();
+    runtime::ret(CLValue::from_t(tax_distributor).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn pools() {
+    let pools = RewardRouter::default().pools();
+    runtime::ret(CLValue::from_t(pools).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn roll_and_get_distribution_address() {
+    let sender: Address = runtime::get_named_arg(SENDER_RUNTIME_ARG_NAME);
+
+    let address = RewardRouter::default()
+        .roll_and_get_distribution_address(sender)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(address).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_distribution_address() {
+    let sender: Address = runtime::get_named_arg(SENDER_RUNTIME_ARG_NAME);
+
+    let address = RewardRouter::default()
+        .get_distribution_address(sender)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(address).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn update_rewards() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+
+    let updated = RewardRouter::default()
+        .update_rewards(address)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(updated).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn call() {
+    let tax_distributor: Address = runtime::get_named_arg(TAX_DISTRIBUTOR_RUNTIME_ARG_NAME);
+
+    let _reward_router = RewardRouter::install(tax_distributor).unwrap_or_revert();
+}
@@ -0,0 +1,46 @@
+//! Constants used by the reward router contract.
+
+/// Name of named-key for `owner`.
+pub const OWNER_KEY_NAME: &str = "owner";
+/// Name of named-key for `pools`.
+pub const POOLS_KEY_NAME: &str = "pools";
+/// Name of named-key for `cursor`.
+pub const CURSOR_KEY_NAME: &str = "cursor";
+/// Name of named-key for `tax_distributor`.
+pub const TAX_DISTRIBUTOR_KEY_NAME: &str = "tax_distributor";
+/// Name of named-key for `contract`.
+pub const REWARD_ROUTER_CONTRACT_KEY_NAME: &str = "reward_router_contract";
+
+/// Upper bound of the number of pools.
+pub const MAX_POOLS: usize = 16;
+
+/// Name of `add_pool` entry point.
+pub const ADD_POOL_ENTRY_POINT_NAME: &str = "add_pool";
+/// Name of `remove_pool` entry point.
+pub const REMOVE_POOL_ENTRY_POINT_NAME: &str = "remove_pool";
+/// Name of `set_pool_weight` entry point.
+pub const SET_POOL_WEIGHT_ENTRY_POINT_NAME: &str = "set_pool_weight";
+/// Name of `set_tax_distributor` entry point.
+pub const SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME: &str = "set_tax_distributor";
+/// Name of `tax_distributor` entry point.
+pub const TAX_DISTRIBUTOR_ENTRY_POINT_NAME: &str = "tax_distributor";
+/// Name of `pools` entry point.
+pub const POOLS_ENTRY_POINT_NAME: &str = "pools";
+/// Name of `roll_and_get_distribution_address` entry point.
+pub const ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str =
+    "roll_and_get_distribution_address";
+/// Name of `get_distribution_address` entry point.
+pub const GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str = "get_distribution_address";
+/// Name of `update_rewards` entry point.
+pub const UPDATE_REWARDS_ENTRY_POINT_NAME: &str = "update_rewards";
+
+/// Name of `pool` runtime argument.
+pub const POOL_RUNTIME_ARG_NAME: &str = "pool";
+/// Name of `weight` runtime argument.
+pub const WEIGHT_RUNTIME_ARG_NAME: &str = "weight";
+/// Name of `tax_distributor` runtime argument.
+pub const TAX_DISTRIBUTOR_RUNTIME_ARG_NAME: &str = "tax_distributor";
+/// Name of `sender` runtime argument.
+pub const SENDER_RUNTIME_ARG_NAME: &str = "sender";
+/// Name of `address` runtime argument.
+pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
@@ -0,0 +1,140 @@
+//! Contains definition of the entry points.
+use alloc::{string::String, vec, vec::Vec};
+
+use casper_types::{
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+};
+
+use casper_erc20::Address;
+
+use crate::constants::{
+    ADDRESS_RUNTIME_ARG_NAME, ADD_POOL_ENTRY_POINT_NAME, GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+    POOLS_ENTRY_POINT_NAME, POOL_RUNTIME_ARG_NAME, REMOVE_POOL_ENTRY_POINT_NAME,
+    ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME, SENDER_RUNTIME_ARG_NAME,
+    SET_POOL_WEIGHT_ENTRY_POINT_NAME, SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME,
+    TAX_DISTRIBUTOR_ENTRY_POINT_NAME, TAX_DISTRIBUTOR_RUNTIME_ARG_NAME,
+    UPDATE_REWARDS_ENTRY_POINT_NAME, WEIGHT_RUNTIME_ARG_NAME,
+};
+
+/// Returns the `add_pool` entry point.
+pub fn add_pool() -> EntryPoint {
+    EntryPoint::new(
+        String::from(ADD_POOL_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(POOL_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(WEIGHT_RUNTIME_ARG_NAME, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `remove_pool` entry point.
+pub fn remove_pool() -> EntryPoint {
+    EntryPoint::new(
+        String::from(REMOVE_POOL_ENTRY_POINT_NAME),
+        vec![Parameter::new(POOL_RUNTIME_ARG_NAME, Address::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_pool_weight` entry point.
+pub fn set_pool_weight() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_POOL_WEIGHT_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(POOL_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(WEIGHT_RUNTIME_ARG_NAME, u32::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_tax_distributor` entry point.
+pub fn set_tax_distributor() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            TAX_DISTRIBUTOR_RUNTIME_ARG_NAME,
+            Address::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `tax_distributor` entry point.
+pub fn tax_distributor() -> EntryPoint {
+    EntryPoint::new(
+        String::from(TAX_DISTRIBUTOR_ENTRY_POINT_NAME),
+        vec![],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `pools` entry point.
+pub fn pools() -> EntryPoint {
+    EntryPoint::new(
+        String::from(POOLS_ENTRY_POINT_NAME),
+        vec![],
+        Vec::<(Address, u32)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `roll_and_get_distribution_address` entry point.
+pub fn roll_and_get_distribution_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME),
+        vec![Parameter::new(SENDER_RUNTIME_ARG_NAME, Address::cl_type())],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `get_distribution_address` entry point, the view of the next target.
+pub fn get_distribution_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME),
+        vec![Parameter::new(SENDER_RUNTIME_ARG_NAME, Address::cl_type())],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `update_rewards` entry point.
+pub fn update_rewards() -> EntryPoint {
+    EntryPoint::new(
+        String::from(UPDATE_REWARDS_ENTRY_POINT_NAME),
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the default set of reward router entry points.
+pub fn default() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(add_pool());
+    entry_points.add_entry_point(remove_pool());
+    entry_points.add_entry_point(set_pool_weight());
+    entry_points.add_entry_point(set_tax_distributor());
+    entry_points.add_entry_point(tax_distributor());
+    entry_points.add_entry_point(pools());
+    entry_points.add_entry_point(roll_and_get_distribution_address());
+    entry_points.add_entry_point(get_distribution_address());
+    entry_points.add_entry_point(update_rewards());
+    entry_points
+}
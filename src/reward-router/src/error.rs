@@ -0,0 +1,59 @@
+//! Error handling on the casper platform.
+use casper_types::ApiError;
+
+/// Errors which can be returned by the reward router.
+///
+/// When an `Error` is returned from a smart contract, it is converted to an [`ApiError::User`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Error {
+    /// Reward router called from within an invalid context.
+    InvalidContext,
+    /// Caller is not the owner of the reward router, or rolls without being its tax distributor.
+    Unauthorized,
+    /// Pool address is not a contract.
+    BadPool,
+    /// Pool is already routed to.
+    DuplicatePool,
+    /// Pool is not routed to.
+    UnknownPool,
+    /// Pool weight is zero.
+    BadWeight,
+    /// Adding the pool would exceed the maximum number of pools.
+    TooManyPools,
+    /// Tax distributor address is not a contract.
+    BadTaxDistributor,
+}
+
+const ERROR_INVALID_CONTEXT: u16 = 1;
+const ERROR_UNAUTHORIZED: u16 = 2;
+const ERROR_BAD_POOL: u16 = 3;
+const ERROR_DUPLICATE_POOL: u16 = 4;
+const ERROR_UNKNOWN_POOL: u16 = 5;
+const ERROR_BAD_WEIGHT: u16 = 6;
+const ERROR_TOO_MANY_POOLS: u16 = 7;
+const ERROR_BAD_TAX_DISTRIBUTOR: u16 = 8;
+
+impl From<casper_contract_utils::Error> for Error {
+    fn from(error: casper_contract_utils::Error) -> Self {
+        match error {
+            casper_contract_utils::Error::InvalidContext => Error::InvalidContext,
+            casper_contract_utils::Error::BadToken => Error::BadPool,
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::Unauthorized => ERROR_UNAUTHORIZED,
+            Error::BadPool => ERROR_BAD_POOL,
+            Error::DuplicatePool => ERROR_DUPLICATE_POOL,
+            Error::UnknownPool => ERROR_UNKNOWN_POOL,
+            Error::BadWeight => ERROR_BAD_WEIGHT,
+            Error::TooManyPools => ERROR_TOO_MANY_POOLS,
+            Error::BadTaxDistributor => ERROR_BAD_TAX_DISTRIBUTOR,
+        };
+        ApiError::User(user_error)
+    }
+}
@@ -0,0 +1,293 @@
+//! Reward distributor rolling rewards across staking pools on the Casper network.
+//!
+//! The router holds a weighted list of staking pools. Every `roll_and_get_distribution_address`
+//! call advances a cursor and names the pool at that slot, so over as many calls as the total
+//! weight each pool is named as often as its weight. A pool that takes no rewards at the moment,
+//! such as a staking pool receiving a stake, leaves its turn's rewards with the tax distributor. `update_rewards` is forwarded to the named
+//! pool. Only the configured tax distributor may roll, so nobody else can skew the rotation.
+
+#![warn(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+pub mod constants;
+pub mod entry_points;
+mod error;
+mod pool;
+
+use alloc::{string::ToString, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_contract_utils::detail;
+use casper_erc20::Address;
+use casper_types::{contracts::NamedKeys, EntryPoints, Key};
+
+use constants::{
+    CURSOR_KEY_NAME, MAX_POOLS, OWNER_KEY_NAME, POOLS_KEY_NAME, REWARD_ROUTER_CONTRACT_KEY_NAME,
+    TAX_DISTRIBUTOR_KEY_NAME,
+};
+use detail::ZERO_ADDRESS;
+pub use error::Error;
+pub use pool::Pool;
+
+/// Implementation of the reward router contract.
+#[derive(Default)]
+pub struct RewardRouter;
+
+impl RewardRouter {
+    /// Reverts with [`Error::Unauthorized`] unless the direct caller is the owner.
+    fn only_owner(&self) -> Result<(), Error> {
+        let caller = detail::get_immediate_caller_address()?;
+        if caller != self.owner() {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Installs the reward router contract with the default set of entry points.
+    ///
+    /// This should be called from within `fn call()` of your contract. The installing account
+    /// becomes the owner allowed to manage the pools, and `tax_distributor` the contract package
+    /// allowed to roll.
+    pub fn install(tax_distributor: Address) -> Result<RewardRouter, Error> {
+        let default_entry_points = entry_points::default();
+        RewardRouter::install_custom(
+            tax_distributor,
+            REWARD_ROUTER_CONTRACT_KEY_NAME,
+            default_entry_points,
+        )
+    }
+
+    /// Returns the owner of the reward router.
+    pub fn owner(&self) -> Address {
+        detail::read_from(OWNER_KEY_NAME)
+    }
+
+    /// Returns the tax distributor allowed to roll.
+    pub fn tax_distributor(&self) -> Address {
+        detail::read_from(TAX_DISTRIBUTOR_KEY_NAME)
+    }
+
+    /// Returns the routed pools and their weights, in routing order.
+    pub fn pools(&self) -> Vec<(Address, u32)> {
+        detail::read_from(POOLS_KEY_NAME)
+    }
+
+    fn cursor(&self) -> u64 {
+        detail::read_from(CURSOR_KEY_NAME)
+    }
+
+    /// Sets the tax distributor contract package allowed to call
+    /// [`roll_and_get_distribution_address`](Self::roll_and_get_distribution_address).
+    pub fn set_tax_distributor(&mut self, tax_distributor: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        check_tax_distributor(tax_distributor)?;
+        detail::write_to(TAX_DISTRIBUTOR_KEY_NAME, tax_distributor);
+        Ok(())
+    }
+
+    /// Adds `pool` with `weight` at the end of the routing order.
+    pub fn add_pool(&mut self, pool: Address, weight: u32) -> Result<(), Error> {
+        self.only_owner()?;
+        Pool::from_address(pool)?;
+        if weight == 0 {
+            return Err(Error::BadWeight);
+        }
+        let mut pools = self.pools();
+        if pools.iter().any(|(address, _)| *address == pool) {
+            return Err(Error::DuplicatePool);
+        }
+        if pools.len() >= MAX_POOLS {
+            return Err(Error::TooManyPools);
+        }
+        pools.push((pool, weight));
+        detail::write_to(POOLS_KEY_NAME, pools);
+        Ok(())
+    }
+
+    /// Stops routing rewards to `pool`.
+    pub fn remove_pool(&mut self, pool: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        let mut pools = self.pools();
+        let index = pools
+            .iter()
+            .position(|(address, _)| *address == pool)
+            .ok_or(Error::UnknownPool)?;
+        pools.remove(index);
+        detail::write_to(POOLS_KEY_NAME, pools);
+        Ok(())
+    }
+
+    /// Changes the weight of `pool`.
+    pub fn set_pool_weight(&mut self, pool: Address, weight: u32) -> Result<(), Error> {
+        self.only_owner()?;
+        if weight == 0 {
+            return Err(Error::BadWeight);
+        }
+        let mut pools = self.pools();
+        let entry = pools
+            .iter_mut()
+            .find(|(address, _)| *address == pool)
+            .ok_or(Error::UnknownPool)?;
+        entry.1 = weight;
+        detail::write_to(POOLS_KEY_NAME, pools);
+        Ok(())
+    }
+
+    /// Returns the pool the next rewards go to and advances the cursor.
+    ///
+    /// Returns the zero address if no pool is routed to, or if the pool at the cursor names no
+    /// distribution address. Fails with [`Error::Unauthorized`]
+    /// unless the direct caller is the tax distributor.
+    pub fn roll_and_get_distribution_address(&mut self, sender: Address) -> Result<Address, Error> {
+        let caller = detail::get_immediate_caller_address()?;
+        check_roller(self.tax_distributor(), caller)?;
+        let cursor = self.cursor();
+        let target = pick(&self.pools(), cursor);
+        if target == ZERO_ADDRESS {
+            return Ok(ZERO_ADDRESS);
+        }
+        detail::write_to(CURSOR_KEY_NAME, cursor.wrapping_add(1));
+        Ok(Pool::from_address(target)?.distribution_address(sender))
+    }
+
+    /// Returns the pool the next [`roll_and_get_distribution_address`] call would name.
+    ///
+    /// [`roll_and_get_distribution_address`]: Self::roll_and_get_distribution_address
+    pub fn get_distribution_address(&self, sender: Address) -> Result<Address, Error> {
+        let target = pick(&self.pools(), self.cursor());
+        if target == ZERO_ADDRESS {
+            return Ok(ZERO_ADDRESS);
+        }
+        Ok(Pool::from_address(target)?.distribution_address(sender))
+    }
+
+    /// Forwards `update_rewards` to `address`. Returns `false` if it is not a routed pool.
+    pub fn update_rewards(&mut self, address: Address) -> Result<bool, Error> {
+        if !self.pools().iter().any(|(pool, _)| *pool == address) {
+            return Ok(false);
+        }
+        Ok(Pool::from_address(address)?.update_rewards())
+    }
+
+    /// Installs the reward router contract with a custom set of entry points.
+    #[doc(hidden)]
+    pub fn install_custom(
+        tax_distributor: Address,
+        contract_key_name: &str,
+        entry_points: EntryPoints,
+    ) -> Result<RewardRouter, Error> {
+        check_tax_distributor(tax_distributor)?;
+        let owner = detail::get_caller_address()?;
+
+        let mut named_keys = NamedKeys::new();
+
+        let owner_key = Key::from(storage::new_uref(owner).into_read());
+        let pools_key =
+            Key::from(storage::new_uref(Vec::<(Address, u32)>::new()).into_read_write());
+        let cursor_key = Key::from(storage::new_uref(0u64).into_read_write());
+        let tax_distributor_key = Key::from(storage::new_uref(tax_distributor).into_read_write());
+
+        named_keys.insert(OWNER_KEY_NAME.to_string(), owner_key);
+        named_keys.insert(POOLS_KEY_NAME.to_string(), pools_key);
+        named_keys.insert(CURSOR_KEY_NAME.to_string(), cursor_key);
+        named_keys.insert(TAX_DISTRIBUTOR_KEY_NAME.to_string(), tax_distributor_key);
+
+        let (contract_hash, _version) =
+            storage::new_locked_contract(entry_points, Some(named_keys), None, None);
+
+        // Hash of the installed contract will be reachable through named keys.
+        runtime::put_key(contract_key_name, Key::from(contract_hash));
+
+        Ok(RewardRouter)
+    }
+}
+
+/// Fails with [`Error::BadTaxDistributor`] unless `tax_distributor` is a contract package.
+fn check_tax_distributor(tax_distributor: Address) -> Result<(), Error> {
+    tax_distributor
+        .as_contract_package_hash()
+        .map(|_| ())
+        .ok_or(Error::BadTaxDistributor)
+}
+
+/// Fails with [`Error::Unauthorized`] unless `caller` is `tax_distributor`, which is never the
+/// zero address.
+fn check_roller(tax_distributor: Address, caller: Address) -> Result<(), Error> {
+    if tax_distributor == ZERO_ADDRESS || caller != tax_distributor {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Returns the pool at slot `cursor % total weight`, walking the pools in order.
+fn pick(pools: &[(Address, u32)], cursor: u64) -> Address {
+    let total_weight: u64 = pools.iter().map(|(_, weight)| *weight as u64).sum();
+    if total_weight == 0 {
+        return ZERO_ADDRESS;
+    }
+    let mut slot = cursor % total_weight;
+    for (pool, weight) in pools {
+        if slot < *weight as u64 {
+            return *pool;
+        }
+        slot -= *weight as u64;
+    }
+    ZERO_ADDRESS
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{account::AccountHash, ContractPackageHash};
+
+    use super::*;
+
+    fn package(i: u8) -> Address {
+        Address::from(ContractPackageHash::new([i + 1; 32]))
+    }
+
+    #[test]
+    fn unset_tax_distributor_cannot_be_installed_or_roll() {
+        assert_eq!(
+            check_tax_distributor(ZERO_ADDRESS),
+            Err(Error::BadTaxDistributor)
+        );
+        assert_eq!(
+            check_tax_distributor(Address::from(AccountHash::new([1; 32]))),
+            Err(Error::BadTaxDistributor)
+        );
+        assert_eq!(check_tax_distributor(package(0)), Ok(()));
+
+        // A router left without a tax distributor rolls for nobody, the zero caller included.
+        assert_eq!(
+            check_roller(ZERO_ADDRESS, ZERO_ADDRESS),
+            Err(Error::Unauthorized)
+        );
+        assert_eq!(
+            check_roller(ZERO_ADDRESS, package(0)),
+            Err(Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn only_the_tax_distributor_rolls() {
+        assert_eq!(check_roller(package(0), package(0)), Ok(()));
+        assert_eq!(
+            check_roller(package(0), package(1)),
+            Err(Error::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn pools_are_picked_by_weight() {
+        let pools = [(package(0), 1), (package(1), 3)];
+        let picks: Vec<Address> = (0..8).map(|cursor| pick(&pools, cursor)).collect();
+        assert_eq!(picks.iter().filter(|pool| **pool == package(0)).count(), 2);
+        assert_eq!(picks.iter().filter(|pool| **pool == package(1)).count(), 6);
+        assert_eq!(pick(&[], 5), ZERO_ADDRESS);
+    }
+}
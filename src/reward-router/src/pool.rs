@@ -0,0 +1,51 @@
+//! Cross-contract reference to a routed staking pool.
+use casper_contract::contract_api::runtime;
+use casper_erc20::Address;
+use casper_types::{runtime_args, ContractPackageHash, RuntimeArgs};
+
+use crate::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+        SENDER_RUNTIME_ARG_NAME, UPDATE_REWARDS_ENTRY_POINT_NAME,
+    },
+    error::Error,
+};
+
+/// Staking pool contract package, asked for its `get_distribution_address(sender)` and notified
+/// through its `update_rewards(address)` entry point.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Pool(ContractPackageHash);
+
+impl Pool {
+    /// Returns the pool installed as the contract package at `address`.
+    pub fn from_address(address: Address) -> Result<Self, Error> {
+        address
+            .as_contract_package_hash()
+            .map(|contract_package_hash| Pool(*contract_package_hash))
+            .ok_or(Error::BadPool)
+    }
+
+    /// Returns the address the pool takes rewards at, or the zero address if it takes none now.
+    pub fn distribution_address(&self, sender: Address) -> Address {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+            runtime_args! {
+                SENDER_RUNTIME_ARG_NAME => sender,
+            },
+        )
+    }
+
+    /// Notifies the pool that rewards were sent to it.
+    pub fn update_rewards(&self) -> bool {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            UPDATE_REWARDS_ENTRY_POINT_NAME,
+            runtime_args! {
+                ADDRESS_RUNTIME_ARG_NAME => Address::from(self.0),
+            },
+        )
+    }
+}
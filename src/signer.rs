@@ -0,0 +1,462 @@
+//! Signers used by relayers to produce bridge withdrawal signatures.
+//!
+//! Bridge logic only talks to [`BridgeSigner`]; whether the key is held in memory, unlocked from
+//! an encrypted keystore, or lives in a separate signing process is a deployment decision.
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use once_cell::unsync::OnceCell;
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_utils::{b2h, h2b, private_to_address, CryptoError, CryptoUtils, EthAddress};
+
+#[derive(Debug)]
+pub enum SignerError {
+    Crypto(CryptoError),
+    #[cfg(feature = "keystore")]
+    Keystore(crate::keystore::KeystoreError),
+    Io(std::io::Error),
+    /// The remote signer answered with something other than a result.
+    Remote(String),
+    /// The remote signer returned a signature that does not recover to its address.
+    WrongSigner {
+        expected: EthAddress,
+        recovered: EthAddress,
+    },
+}
+
+impl fmt::Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignerError::Crypto(e) => write!(f, "signer: {}", e),
+            #[cfg(feature = "keystore")]
+            SignerError::Keystore(e) => write!(f, "signer: {}", e),
+            SignerError::Io(e) => write!(f, "signer io error: {}", e),
+            SignerError::Remote(e) => write!(f, "remote signer: {}", e),
+            SignerError::WrongSigner {
+                expected,
+                recovered,
+            } => write!(
+                f,
+                "remote signer: signature recovers to {}, expected {}",
+                recovered, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+impl From<CryptoError> for SignerError {
+    fn from(e: CryptoError) -> Self {
+        SignerError::Crypto(e)
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl From<crate::keystore::KeystoreError> for SignerError {
+    fn from(e: crate::keystore::KeystoreError) -> Self {
+        SignerError::Keystore(e)
+    }
+}
+
+impl From<std::io::Error> for SignerError {
+    fn from(e: std::io::Error) -> Self {
+        SignerError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SignerError {
+    fn from(e: serde_json::Error) -> Self {
+        SignerError::Remote(e.to_string())
+    }
+}
+
+pub trait BridgeSigner {
+    /// Ethereum style address of the signing key, as recovered by `ecdsa_recover`.
+    fn address(&self) -> Result<EthAddress, SignerError>;
+
+    /// Signs a 32 byte digest, returning the 65 byte `r || s || v` signature.
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, SignerError>;
+}
+
+/// Signer holding the private key in memory.
+pub struct LocalSigner {
+    private_key: Vec<u8>,
+    address: EthAddress,
+}
+
+impl LocalSigner {
+    pub fn new(private_key: Vec<u8>) -> Result<Self, SignerError> {
+        let address = private_to_address(&private_key)?;
+        Ok(LocalSigner {
+            private_key,
+            address,
+        })
+    }
+}
+
+impl BridgeSigner for LocalSigner {
+    fn address(&self) -> Result<EthAddress, SignerError> {
+        Ok(self.address)
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, SignerError> {
+        Ok(CryptoUtils::new().sign(digest, &self.private_key)?)
+    }
+}
+
+/// Signer unlocked from an encrypted keyfile; see [`crate::keystore`].
+#[cfg(feature = "keystore")]
+pub struct KeystoreSigner {
+    inner: LocalSigner,
+}
+
+#[cfg(feature = "keystore")]
+impl KeystoreSigner {
+    pub fn open(path: impl AsRef<std::path::Path>, password: &str) -> Result<Self, SignerError> {
+        let private_key = crate::keystore::load(path, password)?;
+        Ok(KeystoreSigner {
+            inner: LocalSigner::new(private_key)?,
+        })
+    }
+}
+
+#[cfg(feature = "keystore")]
+impl BridgeSigner for KeystoreSigner {
+    fn address(&self) -> Result<EthAddress, SignerError> {
+        self.inner.address()
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, SignerError> {
+        self.inner.sign_digest(digest)
+    }
+}
+
+/// Wire format spoken with a remote signer: one JSON object per request and per response.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum RemoteRequest {
+    Address,
+    SignDigest { digest: String },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RemoteResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum RemoteTransport {
+    /// Newline delimited JSON over a TCP socket, e.g. `127.0.0.1:7070`.
+    Tcp(String),
+    /// Newline delimited JSON over a unix domain socket.
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+    /// JSON POSTed to a plain `http://host:port/path` endpoint.
+    Http(String),
+}
+
+/// Signer delegating to a separate signing process.
+///
+/// The address is fetched once and every returned signature is checked to recover to it, so a
+/// misbehaving signing process cannot slip signatures from another key to the relayer.
+pub struct RemoteSigner {
+    transport: RemoteTransport,
+    address: OnceCell<EthAddress>,
+}
+
+impl RemoteSigner {
+    pub fn new(transport: RemoteTransport) -> Self {
+        RemoteSigner {
+            transport,
+            address: OnceCell::new(),
+        }
+    }
+
+    fn call(&self, request: &RemoteRequest) -> Result<Vec<u8>, SignerError> {
+        let body = serde_json::to_string(request)?;
+        let response = match &self.transport {
+            RemoteTransport::Tcp(addr) => call_line(TcpStream::connect(addr)?, &body)?,
+            #[cfg(unix)]
+            RemoteTransport::Unix(path) => call_line(UnixStream::connect(path)?, &body)?,
+            RemoteTransport::Http(url) => call_http(url, &body)?,
+        };
+
+        let response: RemoteResponse = serde_json::from_str(&response)?;
+        match (response.result, response.error) {
+            (Some(result), None) => Ok(h2b(&result)?),
+            (_, Some(error)) => Err(SignerError::Remote(error)),
+            (None, None) => Err(SignerError::Remote("empty response".into())),
+        }
+    }
+}
+
+impl BridgeSigner for RemoteSigner {
+    fn address(&self) -> Result<EthAddress, SignerError> {
+        self.address
+            .get_or_try_init(|| {
+                let address = self.call(&RemoteRequest::Address)?;
+                Ok(EthAddress::try_from(address.as_slice())?)
+            })
+            .map(|address| *address)
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, SignerError> {
+        let expected = self.address()?;
+        let signature = self.call(&RemoteRequest::SignDigest {
+            digest: b2h(digest),
+        })?;
+        let recovered = CryptoUtils::new().recover(digest, &signature)?;
+        if recovered != expected {
+            return Err(SignerError::WrongSigner {
+                expected,
+                recovered,
+            });
+        }
+        Ok(signature)
+    }
+}
+
+fn call_line<S: Read + Write>(mut stream: S, body: &str) -> Result<String, SignerError> {
+    stream.write_all(body.as_bytes())?;
+    stream.write_all(b"\n")?;
+    stream.flush()?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(line)
+}
+
+fn call_http(url: &str, body: &str) -> Result<String, SignerError> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| SignerError::Remote(format!("unsupported url {}", url)))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let addr = host
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| SignerError::Remote(format!("cannot resolve {}", host)))?;
+
+    let mut stream = TcpStream::connect(addr)?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, payload) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| SignerError::Remote("malformed http response".into()))?;
+    let status = head.lines().next().unwrap_or_default();
+    if !status.contains(" 200 ") {
+        return Err(SignerError::Remote(status.to_string()));
+    }
+    Ok(payload.to_string())
+}
+
+/// Answers one remote signer request with `signer`. Used by the stand-in signing service.
+pub fn handle_remote_request<S: BridgeSigner>(signer: &S, request: &str) -> RemoteResponse {
+    let result = serde_json::from_str::<RemoteRequest>(request)
+        .map_err(SignerError::from)
+        .and_then(|request| match request {
+            RemoteRequest::Address => signer.address().map(|address| address.as_bytes().to_vec()),
+            RemoteRequest::SignDigest { digest } => signer.sign_digest(&h2b(&digest)?),
+        });
+    match result {
+        Ok(bytes) => RemoteResponse {
+            result: Some(b2h(&bytes)),
+            error: None,
+        },
+        Err(e) => RemoteResponse {
+            result: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Minimal stand-in signing service speaking newline delimited JSON over TCP. Meant for local
+/// development and tests of relayers using [`RemoteTransport::Tcp`]; it has no authentication.
+pub fn serve_tcp<S: BridgeSigner>(listener: TcpListener, signer: &S) -> Result<(), SignerError> {
+    for stream in listener.incoming() {
+        serve_line(stream?, signer)?;
+    }
+    Ok(())
+}
+
+/// Unix socket counterpart of [`serve_tcp`], for relayers using [`RemoteTransport::Unix`].
+#[cfg(unix)]
+pub fn serve_unix<S: BridgeSigner>(listener: UnixListener, signer: &S) -> Result<(), SignerError> {
+    for stream in listener.incoming() {
+        serve_line(stream?, signer)?;
+    }
+    Ok(())
+}
+
+/// Stand-in for [`RemoteTransport::Http`]: answers each POST with the JSON response and closes
+/// the connection. Only the subset of HTTP/1.1 that `call_http` speaks is understood.
+pub fn serve_http<S: BridgeSigner>(listener: TcpListener, signer: &S) -> Result<(), SignerError> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let mut reader = BufReader::new(&stream);
+
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value
+                        .trim()
+                        .parse()
+                        .map_err(|_| SignerError::Remote("bad content-length".into()))?;
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+
+        let request = String::from_utf8_lossy(&body);
+        let response = serde_json::to_string(&handle_remote_request(signer, &request))?;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response
+        )?;
+    }
+    Ok(())
+}
+
+fn serve_line<S: BridgeSigner, T: Read + Write>(
+    mut stream: T,
+    signer: &S,
+) -> Result<(), SignerError> {
+    let mut request = String::new();
+    BufReader::new(&mut stream).read_line(&mut request)?;
+    let response = serde_json::to_string(&handle_remote_request(signer, &request))?;
+    stream.write_all(response.as_bytes())?;
+    stream.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    const DIGEST: [u8; 32] = [0x42; 32];
+
+    fn local() -> LocalSigner {
+        LocalSigner::new(vec![0x11; 32]).unwrap()
+    }
+
+    fn tcp_listener() -> (TcpListener, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        (listener, addr)
+    }
+
+    fn assert_round_trip(transport: RemoteTransport) {
+        let expected = local();
+        let remote = RemoteSigner::new(transport);
+        assert_eq!(remote.address().unwrap(), expected.address().unwrap());
+        assert_eq!(
+            remote.sign_digest(&DIGEST).unwrap(),
+            expected.sign_digest(&DIGEST).unwrap()
+        );
+    }
+
+    /// Reports one key's address but signs with another.
+    struct MismatchedSigner {
+        claimed: LocalSigner,
+        actual: LocalSigner,
+    }
+
+    impl BridgeSigner for MismatchedSigner {
+        fn address(&self) -> Result<EthAddress, SignerError> {
+            self.claimed.address()
+        }
+
+        fn sign_digest(&self, digest: &[u8]) -> Result<Vec<u8>, SignerError> {
+            self.actual.sign_digest(digest)
+        }
+    }
+
+    #[test]
+    fn tcp_round_trip() {
+        let (listener, addr) = tcp_listener();
+        thread::spawn(move || serve_tcp(listener, &local()));
+        assert_round_trip(RemoteTransport::Tcp(addr));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_round_trip() {
+        let path = std::env::temp_dir().join(format!("bridge-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        thread::spawn(move || serve_unix(listener, &local()));
+        assert_round_trip(RemoteTransport::Unix(path.clone()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn http_round_trip() {
+        let (listener, addr) = tcp_listener();
+        thread::spawn(move || serve_http(listener, &local()));
+        assert_round_trip(RemoteTransport::Http(format!("http://{}/sign", addr)));
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        let (listener, addr) = tcp_listener();
+        let signer = MismatchedSigner {
+            claimed: local(),
+            actual: LocalSigner::new(vec![0x22; 32]).unwrap(),
+        };
+        let claimed = signer.claimed.address().unwrap();
+        let actual = signer.actual.address().unwrap();
+        thread::spawn(move || serve_tcp(listener, &signer));
+
+        match RemoteSigner::new(RemoteTransport::Tcp(addr)).sign_digest(&DIGEST) {
+            Err(SignerError::WrongSigner {
+                expected,
+                recovered,
+            }) => {
+                assert_eq!(expected, claimed);
+                assert_eq!(recovered, actual);
+            }
+            other => panic!("expected WrongSigner, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn remote_errors_are_surfaced() {
+        let (listener, addr) = tcp_listener();
+        thread::spawn(move || serve_tcp(listener, &local()));
+
+        let error = RemoteSigner::new(RemoteTransport::Tcp(addr))
+            .sign_digest(&[0x42; 31])
+            .unwrap_err();
+        assert!(matches!(error, SignerError::Remote(_)), "{}", error);
+    }
+}
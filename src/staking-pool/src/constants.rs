@@ -0,0 +1,56 @@
+//! Constants used by the staking pool contract.
+
+/// Name of named-key for `staking_token`.
+pub const STAKING_TOKEN_KEY_NAME: &str = "staking_token";
+/// Name of named-key for `reward_token`.
+pub const REWARD_TOKEN_KEY_NAME: &str = "reward_token";
+/// Name of named-key for `total_staked`.
+pub const TOTAL_STAKED_KEY_NAME: &str = "total_staked";
+/// Name of named-key for `reward_per_token`.
+pub const REWARD_PER_TOKEN_KEY_NAME: &str = "reward_per_token";
+/// Name of named-key for `accounted_rewards`.
+pub const ACCOUNTED_REWARDS_KEY_NAME: &str = "accounted_rewards";
+/// Name of named-key for `stake_in_progress`.
+pub const STAKE_IN_PROGRESS_KEY_NAME: &str = "stake_in_progress";
+/// Name of named-key for `contract`.
+pub const STAKING_POOL_CONTRACT_KEY_NAME: &str = "staking_pool_contract";
+/// Name of dictionary-key for `stakes`.
+pub const STAKES_KEY_NAME: &str = "stakes";
+/// Name of dictionary-key for `reward_per_token_paid`.
+pub const REWARD_PER_TOKEN_PAID_KEY_NAME: &str = "reward_per_token_paid";
+/// Name of dictionary-key for `rewards`.
+pub const REWARDS_KEY_NAME: &str = "rewards";
+
+/// Number of decimals `reward_per_token` is scaled by.
+pub const REWARD_PER_TOKEN_DECIMALS: usize = 18;
+
+/// Name of `stake` entry point.
+pub const STAKE_ENTRY_POINT_NAME: &str = "stake";
+/// Name of `unstake` entry point.
+pub const UNSTAKE_ENTRY_POINT_NAME: &str = "unstake";
+/// Name of `claim` entry point.
+pub const CLAIM_ENTRY_POINT_NAME: &str = "claim";
+/// Name of `earned` entry point.
+pub const EARNED_ENTRY_POINT_NAME: &str = "earned";
+/// Name of `staked` entry point.
+pub const STAKED_ENTRY_POINT_NAME: &str = "staked";
+/// Name of `total_staked` entry point.
+pub const TOTAL_STAKED_ENTRY_POINT_NAME: &str = "total_staked";
+/// Name of `roll_and_get_distribution_address` entry point.
+pub const ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str =
+    "roll_and_get_distribution_address";
+/// Name of `get_distribution_address` entry point.
+pub const GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str = "get_distribution_address";
+/// Name of `update_rewards` entry point.
+pub const UPDATE_REWARDS_ENTRY_POINT_NAME: &str = "update_rewards";
+
+/// Name of `staking_token` runtime argument.
+pub const STAKING_TOKEN_RUNTIME_ARG_NAME: &str = "staking_token";
+/// Name of `reward_token` runtime argument.
+pub const REWARD_TOKEN_RUNTIME_ARG_NAME: &str = "reward_token";
+/// Name of `amount` runtime argument.
+pub const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
+/// Name of `address` runtime argument.
+pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
+/// Name of `sender` runtime argument.
+pub const SENDER_RUNTIME_ARG_NAME: &str = "sender";
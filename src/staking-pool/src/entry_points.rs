@@ -0,0 +1,130 @@
+//! Contains definition of the entry points.
+use alloc::{string::String, vec};
+
+use casper_types::{
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter, U256,
+};
+
+use casper_erc20::Address;
+
+use crate::constants::{
+    ADDRESS_RUNTIME_ARG_NAME, AMOUNT_RUNTIME_ARG_NAME, CLAIM_ENTRY_POINT_NAME,
+    EARNED_ENTRY_POINT_NAME, GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+    ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME, SENDER_RUNTIME_ARG_NAME,
+    STAKED_ENTRY_POINT_NAME, STAKE_ENTRY_POINT_NAME, TOTAL_STAKED_ENTRY_POINT_NAME,
+    UNSTAKE_ENTRY_POINT_NAME, UPDATE_REWARDS_ENTRY_POINT_NAME,
+};
+
+/// Returns the `stake` entry point.
+pub fn stake() -> EntryPoint {
+    EntryPoint::new(
+        String::from(STAKE_ENTRY_POINT_NAME),
+        vec![Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `unstake` entry point.
+pub fn unstake() -> EntryPoint {
+    EntryPoint::new(
+        String::from(UNSTAKE_ENTRY_POINT_NAME),
+        vec![Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `claim` entry point.
+pub fn claim() -> EntryPoint {
+    EntryPoint::new(
+        String::from(CLAIM_ENTRY_POINT_NAME),
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `earned` entry point.
+pub fn earned() -> EntryPoint {
+    EntryPoint::new(
+        String::from(EARNED_ENTRY_POINT_NAME),
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `staked` entry point.
+pub fn staked() -> EntryPoint {
+    EntryPoint::new(
+        String::from(STAKED_ENTRY_POINT_NAME),
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `total_staked` entry point.
+pub fn total_staked() -> EntryPoint {
+    EntryPoint::new(
+        String::from(TOTAL_STAKED_ENTRY_POINT_NAME),
+        vec![],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `roll_and_get_distribution_address` entry point.
+pub fn roll_and_get_distribution_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME),
+        vec![Parameter::new(SENDER_RUNTIME_ARG_NAME, Address::cl_type())],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `get_distribution_address` entry point.
+pub fn get_distribution_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME),
+        vec![Parameter::new(SENDER_RUNTIME_ARG_NAME, Address::cl_type())],
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `update_rewards` entry point.
+pub fn update_rewards() -> EntryPoint {
+    EntryPoint::new(
+        String::from(UPDATE_REWARDS_ENTRY_POINT_NAME),
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the default set of staking pool entry points.
+pub fn default() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(stake());
+    entry_points.add_entry_point(unstake());
+    entry_points.add_entry_point(claim());
+    entry_points.add_entry_point(earned());
+    entry_points.add_entry_point(staked());
+    entry_points.add_entry_point(total_staked());
+    entry_points.add_entry_point(roll_and_get_distribution_address());
+    entry_points.add_entry_point(get_distribution_address());
+    entry_points.add_entry_point(update_rewards());
+    entry_points
+}
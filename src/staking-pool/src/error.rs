@@ -0,0 +1,51 @@
+//! Error handling on the casper platform.
+use casper_types::ApiError;
+
+/// Errors which can be returned by the staking pool.
+///
+/// When an `Error` is returned from a smart contract, it is converted to an [`ApiError::User`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Error {
+    /// Staking pool called from within an invalid context.
+    InvalidContext,
+    /// Token address is not a contract.
+    BadToken,
+    /// Amount is zero.
+    ZeroAmount,
+    /// Caller has staked less than the requested amount.
+    InsufficientStake,
+    /// Operation would cause an integer overflow.
+    Overflow,
+    /// Rewards were sent to the pool while a stake was being received.
+    StakeInProgress,
+}
+
+const ERROR_INVALID_CONTEXT: u16 = 1;
+const ERROR_BAD_TOKEN: u16 = 2;
+const ERROR_ZERO_AMOUNT: u16 = 3;
+const ERROR_INSUFFICIENT_STAKE: u16 = 4;
+const ERROR_OVERFLOW: u16 = 5;
+const ERROR_STAKE_IN_PROGRESS: u16 = 6;
+
+impl From<casper_contract_utils::Error> for Error {
+    fn from(error: casper_contract_utils::Error) -> Self {
+        match error {
+            casper_contract_utils::Error::InvalidContext => Error::InvalidContext,
+            casper_contract_utils::Error::BadToken => Error::BadToken,
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::BadToken => ERROR_BAD_TOKEN,
+            Error::ZeroAmount => ERROR_ZERO_AMOUNT,
+            Error::InsufficientStake => ERROR_INSUFFICIENT_STAKE,
+            Error::Overflow => ERROR_OVERFLOW,
+            Error::StakeInProgress => ERROR_STAKE_IN_PROGRESS,
+        };
+        ApiError::User(user_error)
+    }
+}
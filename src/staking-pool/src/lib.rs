@@ -0,0 +1,368 @@
+//! Staking pool for ERC20 tokens on the Casper network.
+//!
+//! Users stake a casper-erc20 token and earn the reward token sent to the pool, in proportion to
+//! their stake. The pool implements the reward distributor interface used by the tax
+//! distributor: it names itself as the distribution address, and `update_rewards` books whatever
+//! reward tokens arrived since the last update.
+//!
+//! A stake is credited with the pool's balance increase over `transfer_from`. If the staking token
+//! is taxed, that transfer can trigger a tax distribution, so while a stake is being received the
+//! pool names no distribution address and refuses `update_rewards`. Rewards then stay with the tax
+//! distributor, and the balance increase is the stake alone.
+
+#![warn(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+pub mod constants;
+pub mod entry_points;
+mod error;
+mod rewards;
+
+use alloc::string::ToString;
+
+use once_cell::unsync::OnceCell;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_contract_utils::{detail, token};
+use casper_erc20::Address;
+use casper_types::{contracts::NamedKeys, EntryPoints, Key, URef, U256};
+
+use constants::{
+    ACCOUNTED_REWARDS_KEY_NAME, REWARDS_KEY_NAME, REWARD_PER_TOKEN_KEY_NAME,
+    REWARD_PER_TOKEN_PAID_KEY_NAME, REWARD_TOKEN_KEY_NAME, STAKES_KEY_NAME,
+    STAKE_IN_PROGRESS_KEY_NAME, STAKING_POOL_CONTRACT_KEY_NAME, STAKING_TOKEN_KEY_NAME,
+    TOTAL_STAKED_KEY_NAME,
+};
+use detail::ZERO_ADDRESS;
+pub use error::Error;
+pub use token::Token;
+
+/// Implementation of the staking pool contract.
+#[derive(Default)]
+pub struct StakingPool {
+    stakes_uref: OnceCell<URef>,
+    reward_per_token_paid_uref: OnceCell<URef>,
+    rewards_uref: OnceCell<URef>,
+}
+
+impl StakingPool {
+    fn new(stakes_uref: URef, reward_per_token_paid_uref: URef, rewards_uref: URef) -> Self {
+        Self {
+            stakes_uref: stakes_uref.into(),
+            reward_per_token_paid_uref: reward_per_token_paid_uref.into(),
+            rewards_uref: rewards_uref.into(),
+        }
+    }
+
+    fn stakes_uref(&self) -> URef {
+        *self
+            .stakes_uref
+            .get_or_init(|| detail::get_uref(STAKES_KEY_NAME))
+    }
+
+    fn reward_per_token_paid_uref(&self) -> URef {
+        *self
+            .reward_per_token_paid_uref
+            .get_or_init(|| detail::get_uref(REWARD_PER_TOKEN_PAID_KEY_NAME))
+    }
+
+    fn rewards_uref(&self) -> URef {
+        *self
+            .rewards_uref
+            .get_or_init(|| detail::get_uref(REWARDS_KEY_NAME))
+    }
+
+    /// Installs the staking pool contract with the default set of entry points.
+    ///
+    /// This should be called from within `fn call()` of your contract.
+    pub fn install(staking_token: Address, reward_token: Address) -> Result<StakingPool, Error> {
+        let default_entry_points = entry_points::default();
+        StakingPool::install_custom(
+            staking_token,
+            reward_token,
+            STAKING_POOL_CONTRACT_KEY_NAME,
+            default_entry_points,
+        )
+    }
+
+    /// Returns the token users stake.
+    pub fn staking_token(&self) -> Address {
+        detail::read_from(STAKING_TOKEN_KEY_NAME)
+    }
+
+    /// Returns the token rewards are paid in.
+    pub fn reward_token(&self) -> Address {
+        detail::read_from(REWARD_TOKEN_KEY_NAME)
+    }
+
+    /// Returns the total amount staked.
+    pub fn total_staked(&self) -> U256 {
+        detail::read_from(TOTAL_STAKED_KEY_NAME)
+    }
+
+    /// Returns the amount staked by `owner`.
+    pub fn staked(&self, owner: Address) -> U256 {
+        detail::read_dictionary(self.stakes_uref(), owner).unwrap_or_default()
+    }
+
+    /// Returns the rewards `owner` could claim now, including rewards not booked yet.
+    pub fn earned(&self, owner: Address) -> Result<U256, Error> {
+        let (reward_per_token, _pending) = self.accrue()?;
+        self.earned_at(owner, reward_per_token)
+    }
+
+    /// Stakes `amount` of the staking token from the caller, who must have approved the pool.
+    ///
+    /// The stake is credited with the amount the pool actually received. No rewards can arrive
+    /// while it is received, so they are never counted as stake.
+    pub fn stake(&mut self, amount: U256) -> Result<(), Error> {
+        if amount.is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        let owner = detail::get_immediate_caller_address()?;
+        self.update_account(owner)?;
+
+        let staking_token = Token::from_address(self.staking_token())?;
+        let pool = token::self_address();
+        let balance_before = staking_token.balance_of(pool);
+        detail::write_to(STAKE_IN_PROGRESS_KEY_NAME, true);
+        staking_token.transfer_from(owner, pool, amount);
+        detail::write_to(STAKE_IN_PROGRESS_KEY_NAME, false);
+        let received = staking_token
+            .balance_of(pool)
+            .checked_sub(balance_before)
+            .ok_or(Error::Overflow)?;
+
+        let staked = self
+            .staked(owner)
+            .checked_add(received)
+            .ok_or(Error::Overflow)?;
+        let total_staked = self
+            .total_staked()
+            .checked_add(received)
+            .ok_or(Error::Overflow)?;
+        detail::write_dictionary(self.stakes_uref(), owner, staked);
+        detail::write_to(TOTAL_STAKED_KEY_NAME, total_staked);
+        Ok(())
+    }
+
+    /// Returns `amount` of the caller's stake to the caller. Earned rewards stay claimable.
+    pub fn unstake(&mut self, amount: U256) -> Result<(), Error> {
+        if amount.is_zero() {
+            return Err(Error::ZeroAmount);
+        }
+        let owner = detail::get_immediate_caller_address()?;
+        self.update_account(owner)?;
+
+        let staked = self
+            .staked(owner)
+            .checked_sub(amount)
+            .ok_or(Error::InsufficientStake)?;
+        let total_staked = self
+            .total_staked()
+            .checked_sub(amount)
+            .ok_or(Error::Overflow)?;
+        detail::write_dictionary(self.stakes_uref(), owner, staked);
+        detail::write_to(TOTAL_STAKED_KEY_NAME, total_staked);
+
+        Token::from_address(self.staking_token())?.transfer(owner, amount);
+        Ok(())
+    }
+
+    /// Pays the caller's rewards out and returns the amount paid.
+    pub fn claim(&mut self) -> Result<U256, Error> {
+        let owner = detail::get_immediate_caller_address()?;
+        self.update_account(owner)?;
+
+        let reward = self.rewards(owner);
+        if reward.is_zero() {
+            return Ok(reward);
+        }
+        let accounted_rewards = self
+            .accounted_rewards()
+            .checked_sub(reward)
+            .ok_or(Error::Overflow)?;
+        detail::write_dictionary(self.rewards_uref(), owner, U256::zero());
+        detail::write_to(ACCOUNTED_REWARDS_KEY_NAME, accounted_rewards);
+
+        Token::from_address(self.reward_token())?.transfer(owner, reward);
+        Ok(reward)
+    }
+
+    /// Returns the address rewards should be sent to: the pool itself, or the zero address while
+    /// a stake is being received.
+    pub fn roll_and_get_distribution_address(&mut self, sender: Address) -> Address {
+        self.get_distribution_address(sender)
+    }
+
+    /// Returns the address [`roll_and_get_distribution_address`] would return.
+    ///
+    /// [`roll_and_get_distribution_address`]: Self::roll_and_get_distribution_address
+    pub fn get_distribution_address(&self, _sender: Address) -> Address {
+        if self.stake_in_progress() {
+            return ZERO_ADDRESS;
+        }
+        token::self_address()
+    }
+
+    /// Books the reward tokens received since the last update.
+    ///
+    /// Returns `false` if `address` is not the pool. Anyone may call this. Fails with
+    /// [`Error::StakeInProgress`] while a stake is being received, since the pool's balance then
+    /// holds stake that is not counted yet.
+    pub fn update_rewards(&mut self, address: Address) -> Result<bool, Error> {
+        if address != token::self_address() {
+            return Ok(false);
+        }
+        if self.stake_in_progress() {
+            return Err(Error::StakeInProgress);
+        }
+        self.sync_rewards()?;
+        Ok(true)
+    }
+
+    fn stake_in_progress(&self) -> bool {
+        detail::read_from(STAKE_IN_PROGRESS_KEY_NAME)
+    }
+
+    fn reward_per_token(&self) -> U256 {
+        detail::read_from(REWARD_PER_TOKEN_KEY_NAME)
+    }
+
+    fn accounted_rewards(&self) -> U256 {
+        detail::read_from(ACCOUNTED_REWARDS_KEY_NAME)
+    }
+
+    fn rewards(&self, owner: Address) -> U256 {
+        detail::read_dictionary(self.rewards_uref(), owner).unwrap_or_default()
+    }
+
+    fn reward_per_token_paid(&self, owner: Address) -> U256 {
+        detail::read_dictionary(self.reward_per_token_paid_uref(), owner).unwrap_or_default()
+    }
+
+    /// Returns the reward tokens held by the pool that are neither stake nor booked rewards.
+    fn pending_rewards(&self) -> Result<U256, Error> {
+        let reward_token = self.reward_token();
+        let balance = Token::from_address(reward_token)?.balance_of(token::self_address());
+        rewards::pending_rewards(
+            balance,
+            self.accounted_rewards(),
+            self.total_staked(),
+            reward_token == self.staking_token(),
+        )
+    }
+
+    /// Returns the reward per token after booking pending rewards, and the amount booked.
+    ///
+    /// Rewards arriving while nothing is staked are left pending for the next stakers.
+    fn accrue(&self) -> Result<(U256, U256), Error> {
+        let reward_per_token = self.reward_per_token();
+        let total_staked = self.total_staked();
+        if total_staked.is_zero() {
+            return Ok((reward_per_token, U256::zero()));
+        }
+        let pending = self.pending_rewards()?;
+        let reward_per_token = rewards::accrue(reward_per_token, pending, total_staked)?;
+        Ok((reward_per_token, pending))
+    }
+
+    fn sync_rewards(&mut self) -> Result<(), Error> {
+        let (reward_per_token, pending) = self.accrue()?;
+        if pending.is_zero() {
+            return Ok(());
+        }
+        let accounted_rewards = self
+            .accounted_rewards()
+            .checked_add(pending)
+            .ok_or(Error::Overflow)?;
+        detail::write_to(REWARD_PER_TOKEN_KEY_NAME, reward_per_token);
+        detail::write_to(ACCOUNTED_REWARDS_KEY_NAME, accounted_rewards);
+        Ok(())
+    }
+
+    fn earned_at(&self, owner: Address, reward_per_token: U256) -> Result<U256, Error> {
+        rewards::earned(
+            self.staked(owner),
+            reward_per_token,
+            self.reward_per_token_paid(owner),
+            self.rewards(owner),
+        )
+    }
+
+    /// Books pending rewards and moves what `owner` earned so far into their rewards.
+    fn update_account(&mut self, owner: Address) -> Result<(), Error> {
+        self.sync_rewards()?;
+        let reward_per_token = self.reward_per_token();
+        let rewards = self.earned_at(owner, reward_per_token)?;
+        detail::write_dictionary(self.rewards_uref(), owner, rewards);
+        detail::write_dictionary(self.reward_per_token_paid_uref(), owner, reward_per_token);
+        Ok(())
+    }
+
+    /// Installs the staking pool contract with a custom set of entry points.
+    #[doc(hidden)]
+    pub fn install_custom(
+        staking_token: Address,
+        reward_token: Address,
+        contract_key_name: &str,
+        entry_points: EntryPoints,
+    ) -> Result<StakingPool, Error> {
+        Token::from_address(staking_token)?;
+        Token::from_address(reward_token)?;
+
+        let stakes_uref = storage::new_dictionary(STAKES_KEY_NAME).unwrap_or_revert();
+        let reward_per_token_paid_uref =
+            storage::new_dictionary(REWARD_PER_TOKEN_PAID_KEY_NAME).unwrap_or_revert();
+        let rewards_uref = storage::new_dictionary(REWARDS_KEY_NAME).unwrap_or_revert();
+
+        let mut named_keys = NamedKeys::new();
+
+        let staking_token_key = Key::from(storage::new_uref(staking_token).into_read());
+        let reward_token_key = Key::from(storage::new_uref(reward_token).into_read());
+
+        for (name, uref) in [
+            (STAKES_KEY_NAME, stakes_uref),
+            (REWARD_PER_TOKEN_PAID_KEY_NAME, reward_per_token_paid_uref),
+            (REWARDS_KEY_NAME, rewards_uref),
+        ] {
+            runtime::remove_key(name);
+            named_keys.insert(name.to_string(), Key::from(uref));
+        }
+
+        for name in [
+            TOTAL_STAKED_KEY_NAME,
+            REWARD_PER_TOKEN_KEY_NAME,
+            ACCOUNTED_REWARDS_KEY_NAME,
+        ] {
+            let key = Key::from(storage::new_uref(U256::zero()).into_read_write());
+            named_keys.insert(name.to_string(), key);
+        }
+
+        let stake_in_progress_key = Key::from(storage::new_uref(false).into_read_write());
+
+        named_keys.insert(STAKING_TOKEN_KEY_NAME.to_string(), staking_token_key);
+        named_keys.insert(REWARD_TOKEN_KEY_NAME.to_string(), reward_token_key);
+        named_keys.insert(
+            STAKE_IN_PROGRESS_KEY_NAME.to_string(),
+            stake_in_progress_key,
+        );
+
+        let (contract_hash, _version) =
+            storage::new_locked_contract(entry_points, Some(named_keys), None, None);
+
+        // Hash of the installed contract will be reachable through named keys.
+        runtime::put_key(contract_key_name, Key::from(contract_hash));
+
+        Ok(StakingPool::new(
+            stakes_uref,
+            reward_per_token_paid_uref,
+            rewards_uref,
+        ))
+    }
+}
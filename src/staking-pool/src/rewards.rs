@@ -0,0 +1,258 @@
+//! Reward-per-token accounting of the staking pool.
+use casper_types::U256;
+
+use crate::{constants::REWARD_PER_TOKEN_DECIMALS, error::Error};
+
+/// Returns the reward tokens held by the pool that are neither stake nor booked rewards.
+///
+/// `balance` is the pool's balance of the reward token. If the reward token is also the staking
+/// token, `total_staked` of that balance is principal.
+pub(crate) fn pending_rewards(
+    balance: U256,
+    accounted_rewards: U256,
+    total_staked: U256,
+    reward_is_staking_token: bool,
+) -> Result<U256, Error> {
+    let mut held = accounted_rewards;
+    if reward_is_staking_token {
+        held = held.checked_add(total_staked).ok_or(Error::Overflow)?;
+    }
+    Ok(balance.saturating_sub(held))
+}
+
+/// Returns `reward_per_token` after spreading `pending` rewards over `total_staked`.
+///
+/// Nothing is spread while nothing is staked.
+pub(crate) fn accrue(
+    reward_per_token: U256,
+    pending: U256,
+    total_staked: U256,
+) -> Result<U256, Error> {
+    if total_staked.is_zero() {
+        return Ok(reward_per_token);
+    }
+    pending
+        .checked_mul(U256::exp10(REWARD_PER_TOKEN_DECIMALS))
+        .map(|scaled| scaled / total_staked)
+        .and_then(|increase| reward_per_token.checked_add(increase))
+        .ok_or(Error::Overflow)
+}
+
+/// Returns `rewards` plus what `staked` earned since `reward_per_token_paid`.
+pub(crate) fn earned(
+    staked: U256,
+    reward_per_token: U256,
+    reward_per_token_paid: U256,
+    rewards: U256,
+) -> Result<U256, Error> {
+    let unpaid = reward_per_token
+        .checked_sub(reward_per_token_paid)
+        .ok_or(Error::Overflow)?;
+    staked
+        .checked_mul(unpaid)
+        .map(|scaled| scaled / U256::exp10(REWARD_PER_TOKEN_DECIMALS))
+        .and_then(|earned| rewards.checked_add(earned))
+        .ok_or(Error::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    const USERS: usize = 3;
+
+    #[derive(Default, Clone, Copy)]
+    struct Account {
+        staked: U256,
+        reward_per_token_paid: U256,
+        rewards: U256,
+    }
+
+    /// The pool's bookkeeping, with one token both staked and paid as reward.
+    #[derive(Default)]
+    struct Ledger {
+        balance: U256,
+        total_staked: U256,
+        accounted_rewards: U256,
+        reward_per_token: U256,
+        accounts: [Account; USERS],
+    }
+
+    impl Ledger {
+        fn sync(&mut self) {
+            if self.total_staked.is_zero() {
+                return;
+            }
+            let pending = pending_rewards(
+                self.balance,
+                self.accounted_rewards,
+                self.total_staked,
+                true,
+            )
+            .unwrap();
+            self.reward_per_token =
+                accrue(self.reward_per_token, pending, self.total_staked).unwrap();
+            self.accounted_rewards += pending;
+        }
+
+        fn update_account(&mut self, user: usize) {
+            self.sync();
+            let account = &mut self.accounts[user];
+            account.rewards = earned(
+                account.staked,
+                self.reward_per_token,
+                account.reward_per_token_paid,
+                account.rewards,
+            )
+            .unwrap();
+            account.reward_per_token_paid = self.reward_per_token;
+        }
+
+        fn earned(&self, user: usize) -> U256 {
+            let pending = pending_rewards(
+                self.balance,
+                self.accounted_rewards,
+                self.total_staked,
+                true,
+            )
+            .unwrap();
+            let reward_per_token =
+                accrue(self.reward_per_token, pending, self.total_staked).unwrap();
+            let account = &self.accounts[user];
+            earned(
+                account.staked,
+                reward_per_token,
+                account.reward_per_token_paid,
+                account.rewards,
+            )
+            .unwrap()
+        }
+
+        fn reward(&mut self, amount: u64) {
+            self.balance += U256::from(amount);
+            self.sync();
+        }
+
+        fn stake(&mut self, user: usize, amount: u64) {
+            self.update_account(user);
+            self.balance += U256::from(amount);
+            self.accounts[user].staked += U256::from(amount);
+            self.total_staked += U256::from(amount);
+        }
+
+        fn unstake(&mut self, user: usize, amount: u64) -> U256 {
+            self.update_account(user);
+            let amount = U256::from(amount);
+            self.accounts[user].staked -= amount;
+            self.total_staked -= amount;
+            self.balance -= amount;
+            amount
+        }
+
+        fn claim(&mut self, user: usize) -> U256 {
+            self.update_account(user);
+            let reward = core::mem::take(&mut self.accounts[user].rewards);
+            self.accounted_rewards -= reward;
+            self.balance -= reward;
+            reward
+        }
+    }
+
+    #[test]
+    fn reward_per_token_accrues_in_proportion_to_stake() {
+        assert_eq!(
+            accrue(U256::zero(), U256::from(5), U256::from(10)).unwrap(),
+            U256::exp10(REWARD_PER_TOKEN_DECIMALS) / 2
+        );
+        assert_eq!(
+            accrue(U256::one(), U256::from(5), U256::zero()).unwrap(),
+            U256::one()
+        );
+
+        let mut ledger = Ledger::default();
+        ledger.stake(0, 100);
+        ledger.stake(1, 300);
+        ledger.reward(400);
+        assert_eq!(ledger.earned(0), U256::from(100));
+        assert_eq!(ledger.earned(1), U256::from(300));
+    }
+
+    #[test]
+    fn stakers_only_earn_rewards_arriving_after_they_stake() {
+        let mut ledger = Ledger::default();
+        ledger.stake(0, 100);
+        ledger.reward(50);
+        ledger.stake(1, 100);
+        assert_eq!(ledger.earned(1), U256::zero());
+        ledger.reward(50);
+        assert_eq!(ledger.earned(0), U256::from(75));
+        assert_eq!(ledger.earned(1), U256::from(25));
+    }
+
+    #[test]
+    fn principal_is_never_booked_as_reward() {
+        let mut ledger = Ledger::default();
+        ledger.stake(0, 1_000);
+        ledger.stake(1, 500);
+        ledger.sync();
+        assert!(ledger.accounted_rewards.is_zero());
+        assert_eq!(ledger.earned(0), U256::zero());
+
+        // Rewards sent while nothing is staked wait for the next stakers.
+        ledger.unstake(0, 1_000);
+        ledger.unstake(1, 500);
+        ledger.reward(30);
+        ledger.stake(2, 10);
+        ledger.reward(0);
+        assert_eq!(ledger.earned(2), U256::from(30));
+    }
+
+    #[test]
+    fn unstake_keeps_earned_rewards_and_claim_pays_them_once() {
+        let mut ledger = Ledger::default();
+        ledger.stake(0, 100);
+        ledger.reward(60);
+        assert_eq!(ledger.unstake(0, 100), U256::from(100));
+        assert_eq!(ledger.earned(0), U256::from(60));
+
+        ledger.reward(40);
+        assert_eq!(ledger.earned(0), U256::from(60));
+        assert_eq!(ledger.claim(0), U256::from(60));
+        assert_eq!(ledger.claim(0), U256::zero());
+        assert_eq!(ledger.balance, U256::from(40));
+    }
+
+    #[test]
+    fn random_operations_never_pay_out_principal_as_reward() {
+        for seed in 1..=200u64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut ledger = Ledger::default();
+            let mut rewarded = U256::zero();
+            let mut claimed = U256::zero();
+
+            for _ in 0..200 {
+                let user = rng.gen_range(0..USERS);
+                let amount = rng.gen_range(1..=1_000u64);
+                match rng.gen_range(0..4) {
+                    0 => ledger.stake(user, amount),
+                    1 if ledger.accounts[user].staked >= U256::from(amount) => {
+                        ledger.unstake(user, amount);
+                    }
+                    2 => {
+                        ledger.reward(amount);
+                        rewarded += U256::from(amount);
+                    }
+                    _ => claimed += ledger.claim(user),
+                }
+                assert!(claimed <= rewarded, "seed {}", seed);
+                assert!(
+                    ledger.balance >= ledger.total_staked + ledger.accounted_rewards,
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20::Address;
+use casper_staking_pool::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, AMOUNT_RUNTIME_ARG_NAME, REWARD_TOKEN_RUNTIME_ARG_NAME,
+        SENDER_RUNTIME_ARG_NAME, STAKING_TOKEN_RUNTIME_ARG_NAME,
+    },
+    StakingPool,
+};
+use casper_types::{CLValue, U256};
+
+#[no_mangle]
+pub extern "C" fn stake() {
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    StakingPool::default().stake(amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn unstake() {
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    StakingPool::default().unstake(amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn claim() {
+    let reward = StakingPool::default().claim().unwrap_or_revert();
+    runtime::ret(CLValue::from_t(reward).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn earned() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+
+    let earned = StakingPool::default().earned(address).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(earned).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn staked() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+
+    let staked = StakingPool::default().staked(address);
+    runtime::ret(CLValue::from_t(staked).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn total_staked() {
+    let total_staked = StakingPool::default().total_staked();
+    runtime::ret(CLValue::from_t(total_staked).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn roll_and_get_distribution_address() {
+    let sender: Address = runtime::get_named_arg(SENDER_RUNTIME_ARG_NAME);
+
+    let address = StakingPool::default().roll_and_get_distribution_address(sender);
+    runtime::ret(CLValue::from_t(address).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_distribution_address() {
+    let sender: Address = runtime::get_named_arg(SENDER_RUNTIME_ARG_NAME);
+
+    let address = StakingPool::default().get_distribution_address(sender);
+    runtime::ret(CLValue::from_t(address).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn update_rewards() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+
+    let updated = StakingPool::default()
+        .update_rewards(address)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(updated).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn call() {
+    let staking_token: Address = runtime::get_named_arg(STAKING_TOKEN_RUNTIME_ARG_NAME);
+    let reward_token: Address = runtime::get_named_arg(REWARD_TOKEN_RUNTIME_ARG_NAME);
+
+    let _staking_pool = StakingPool::install(staking_token, reward_token).unwrap_or_revert();
+}
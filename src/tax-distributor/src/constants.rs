@@ -0,0 +1,107 @@
+//! Constants used by the tax distributor contract.
+
+/// Name of named-key for `owner`.
+pub const OWNER_KEY_NAME: &str = "owner";
+/// Name of named-key for `global_dev_address`.
+pub const GLOBAL_DEV_ADDRESS_KEY_NAME: &str = "global_dev_address";
+/// Name of named-key for `global_dev_fee_per_100`.
+pub const GLOBAL_DEV_FEE_PER_100_KEY_NAME: &str = "global_dev_fee_per_100";
+/// Name of named-key for `dust_recipient`.
+pub const DUST_RECIPIENT_KEY_NAME: &str = "dust_recipient";
+/// Name of named-key for `pull_payments`.
+pub const PULL_PAYMENTS_KEY_NAME: &str = "pull_payments";
+/// Name of named-key for `contract`.
+pub const TAX_DISTRIBUTOR_CONTRACT_KEY_NAME: &str = "tax_distributor_contract";
+/// Name of dictionary-key for `distributions`.
+pub const DISTRIBUTIONS_KEY_NAME: &str = "distributions";
+/// Name of dictionary-key for `reward_distributors`.
+pub const REWARD_DISTRIBUTORS_KEY_NAME: &str = "reward_distributors";
+/// Name of dictionary-key for `dev_addresses`.
+pub const DEV_ADDRESSES_KEY_NAME: &str = "dev_addresses";
+/// Name of dictionary-key for `future_addresses`.
+pub const FUTURE_ADDRESSES_KEY_NAME: &str = "future_addresses";
+/// Name of dictionary-key for `burn_addresses`.
+pub const BURN_ADDRESSES_KEY_NAME: &str = "burn_addresses";
+/// Name of dictionary-key for `claimable`.
+pub const CLAIMABLE_KEY_NAME: &str = "claimable";
+/// Name of dictionary-key for `total_claimable`.
+pub const TOTAL_CLAIMABLE_KEY_NAME: &str = "total_claimable";
+
+/// Upper bound of `global_dev_fee_per_100`.
+pub const MAX_GLOBAL_DEV_FEE_PER_100: u8 = 100;
+/// Sum of the weights of a distribution, in basis points.
+pub const TOTAL_WEIGHT_BPS: u16 = 10_000;
+/// Upper bound of the number of recipients in a distribution.
+pub const MAX_DISTRIBUTION_RECIPIENTS: usize = 16;
+
+/// Name of `set_reward_distributor` entry point.
+pub const SET_REWARD_DISTRIBUTOR_ENTRY_POINT_NAME: &str = "set_reward_distributor";
+/// Name of `set_dev_address` entry point.
+pub const SET_DEV_ADDRESS_ENTRY_POINT_NAME: &str = "set_dev_address";
+/// Name of `set_global_dev_address` entry point.
+pub const SET_GLOBAL_DEV_ADDRESS_ENTRY_POINT_NAME: &str = "set_global_dev_address";
+/// Name of `set_future_address` entry point.
+pub const SET_FUTURE_ADDRESS_ENTRY_POINT_NAME: &str = "set_future_address";
+/// Name of `set_burn_address` entry point.
+pub const SET_BURN_ADDRESS_ENTRY_POINT_NAME: &str = "set_burn_address";
+/// Name of `set_default_distribution` entry point.
+pub const SET_DEFAULT_DISTRIBUTION_ENTRY_POINT_NAME: &str = "set_default_distribution";
+/// Name of `set_distribution` entry point.
+pub const SET_DISTRIBUTION_ENTRY_POINT_NAME: &str = "set_distribution";
+/// Name of `set_dust_recipient` entry point.
+pub const SET_DUST_RECIPIENT_ENTRY_POINT_NAME: &str = "set_dust_recipient";
+/// Name of `set_pull_payments` entry point.
+pub const SET_PULL_PAYMENTS_ENTRY_POINT_NAME: &str = "set_pull_payments";
+/// Name of `preview_distribution` entry point.
+pub const PREVIEW_DISTRIBUTION_ENTRY_POINT_NAME: &str = "preview_distribution";
+/// Name of `distribute_tax` entry point.
+pub const DISTRIBUTE_TAX_ENTRY_POINT_NAME: &str = "distribute_tax";
+/// Name of `distributable_balance` entry point.
+pub const DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME: &str = "distributable_balance";
+/// Name of `claimable` entry point.
+pub const CLAIMABLE_ENTRY_POINT_NAME: &str = "claimable";
+/// Name of `claim` entry point.
+pub const CLAIM_ENTRY_POINT_NAME: &str = "claim";
+
+/// Name of `roll_and_get_distribution_address` entry point of a reward distributor.
+pub const ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str =
+    "roll_and_get_distribution_address";
+/// Name of `get_distribution_address` entry point of a reward distributor.
+pub const GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME: &str = "get_distribution_address";
+/// Name of `update_rewards` entry point of a reward distributor.
+pub const UPDATE_REWARDS_ENTRY_POINT_NAME: &str = "update_rewards";
+
+/// Name of `token` runtime argument.
+pub const TOKEN_RUNTIME_ARG_NAME: &str = "token";
+/// Name of `reward_distributor` runtime argument.
+pub const REWARD_DISTRIBUTOR_RUNTIME_ARG_NAME: &str = "reward_distributor";
+/// Name of `dev_address` runtime argument.
+pub const DEV_ADDRESS_RUNTIME_ARG_NAME: &str = "dev_address";
+/// Name of `dev_fee_per_100` runtime argument.
+pub const DEV_FEE_PER_100_RUNTIME_ARG_NAME: &str = "dev_fee_per_100";
+/// Name of `future_address` runtime argument.
+pub const FUTURE_ADDRESS_RUNTIME_ARG_NAME: &str = "future_address";
+/// Name of `burn_address` runtime argument.
+pub const BURN_ADDRESS_RUNTIME_ARG_NAME: &str = "burn_address";
+/// Name of `stake` runtime argument.
+pub const STAKE_RUNTIME_ARG_NAME: &str = "stake";
+/// Name of `burn` runtime argument.
+pub const BURN_RUNTIME_ARG_NAME: &str = "burn";
+/// Name of `dev` runtime argument.
+pub const DEV_RUNTIME_ARG_NAME: &str = "dev";
+/// Name of `future` runtime argument.
+pub const FUTURE_RUNTIME_ARG_NAME: &str = "future";
+/// Name of `distribution` runtime argument.
+pub const DISTRIBUTION_RUNTIME_ARG_NAME: &str = "distribution";
+/// Name of `dust_recipient` runtime argument.
+pub const DUST_RECIPIENT_RUNTIME_ARG_NAME: &str = "dust_recipient";
+/// Name of `pull_payments` runtime argument.
+pub const PULL_PAYMENTS_RUNTIME_ARG_NAME: &str = "pull_payments";
+/// Name of `recipient` runtime argument.
+pub const RECIPIENT_RUNTIME_ARG_NAME: &str = "recipient";
+/// Name of `amount` runtime argument.
+pub const AMOUNT_RUNTIME_ARG_NAME: &str = "amount";
+/// Name of `sender` runtime argument.
+pub const SENDER_RUNTIME_ARG_NAME: &str = "sender";
+/// Name of `address` runtime argument.
+pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
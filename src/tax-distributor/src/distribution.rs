@@ -0,0 +1,613 @@
+//! Per-token split of collected tax.
+use alloc::{boxed::Box, vec::Vec};
+
+use casper_erc20::Address;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, U256,
+};
+
+use crate::{
+    constants::{MAX_DISTRIBUTION_RECIPIENTS, TOTAL_WEIGHT_BPS},
+    detail::ZERO_ADDRESS,
+    error::Error,
+};
+
+/// What happens to the share of a [`DistributionEntry`].
+#[repr(u8)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RecipientKind {
+    /// Share is transferred to the target address.
+    Transfer = 0,
+    /// Share is burned through the token's `burn` entry point, or, for tokens without one,
+    /// transferred to the target burn address if it is not the zero address.
+    Burn = 1,
+    /// Share is sent to the address picked by the target reward distributor contract.
+    RewardDistributor = 2,
+}
+
+impl RecipientKind {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(RecipientKind::Transfer),
+            1 => Some(RecipientKind::Burn),
+            2 => Some(RecipientKind::RewardDistributor),
+            _ => None,
+        }
+    }
+}
+
+/// One recipient of a token's tax and its weight in basis points.
+///
+/// Serialized as a `(u8, Key, u16)` tuple of kind, target and weight.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct DistributionEntry {
+    /// What to do with the share.
+    pub kind: RecipientKind,
+    /// Recipient address, or reward distributor contract package.
+    pub target: Address,
+    /// Share of the distributed amount, in basis points.
+    pub weight_bps: u16,
+}
+
+impl DistributionEntry {
+    fn to_tuple(self) -> (u8, Address, u16) {
+        (self.kind as u8, self.target, self.weight_bps)
+    }
+}
+
+impl CLTyped for DistributionEntry {
+    fn cl_type() -> CLType {
+        CLType::Tuple3([
+            Box::new(u8::cl_type()),
+            Box::new(Address::cl_type()),
+            Box::new(u16::cl_type()),
+        ])
+    }
+}
+
+impl ToBytes for DistributionEntry {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.to_tuple().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.to_tuple().serialized_length()
+    }
+}
+
+impl FromBytes for DistributionEntry {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let ((kind, target, weight_bps), remainder) = <(u8, Address, u16)>::from_bytes(bytes)?;
+        let kind = RecipientKind::from_u8(kind).ok_or(bytesrepr::Error::Formatting)?;
+        Ok((
+            DistributionEntry {
+                kind,
+                target,
+                weight_bps,
+            },
+            remainder,
+        ))
+    }
+}
+
+/// Weighted list of recipients of a token's tax.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Distribution(Vec<DistributionEntry>);
+
+impl Distribution {
+    /// Creates a distribution, checking that it has at least one and at most
+    /// [`MAX_DISTRIBUTION_RECIPIENTS`] entries, that transfer and reward distributor targets are
+    /// set, and that the weights add up to [`TOTAL_WEIGHT_BPS`].
+    pub fn new(entries: Vec<DistributionEntry>) -> Result<Self, Error> {
+        if entries.is_empty() || entries.len() > MAX_DISTRIBUTION_RECIPIENTS {
+            return Err(Error::InvalidDistribution);
+        }
+        let mut total: u32 = 0;
+        for entry in &entries {
+            match entry.kind {
+                RecipientKind::Transfer if entry.target == ZERO_ADDRESS => {
+                    return Err(Error::BadRecipient)
+                }
+                RecipientKind::RewardDistributor
+                    if entry.target.as_contract_package_hash().is_none() =>
+                {
+                    return Err(Error::BadRewardDistributor)
+                }
+                _ => {}
+            }
+            total += entry.weight_bps as u32;
+        }
+        if total != TOTAL_WEIGHT_BPS as u32 {
+            return Err(Error::InvalidDistribution);
+        }
+        Ok(Distribution(entries))
+    }
+
+    /// Returns the recipients.
+    pub fn entries(&self) -> &[DistributionEntry] {
+        &self.0
+    }
+
+    /// Returns `amount * weight_bps / 10000` for `entry`.
+    pub fn share_of(entry: &DistributionEntry, amount: U256) -> Result<U256, Error> {
+        amount
+            .checked_mul(U256::from(entry.weight_bps))
+            .map(|product| product / U256::from(TOTAL_WEIGHT_BPS))
+            .ok_or(Error::Overflow)
+    }
+
+    /// Splits `amount` into the global dev fee, one share per entry, and the rounding dust.
+    ///
+    /// Shares are rounded down and the dust is whatever is left, so the parts always add up to
+    /// `amount` exactly.
+    pub fn split(&self, amount: U256, global_dev_fee_per_100: U256) -> Result<Split, Error> {
+        let global_dev = percent_of(amount, global_dev_fee_per_100)?;
+        let net = amount.checked_sub(global_dev).ok_or(Error::Overflow)?;
+        let mut shares = Vec::with_capacity(self.0.len());
+        let mut dust = net;
+        for entry in &self.0 {
+            let share = Distribution::share_of(entry, net)?;
+            dust = dust.checked_sub(share).ok_or(Error::Overflow)?;
+            shares.push(share);
+        }
+        Ok(Split {
+            global_dev,
+            shares,
+            dust,
+        })
+    }
+
+    /// Returns the part of `balance` that can be distributed, leaving out `total_claimable`, which
+    /// is already owed to recipients that have not claimed it yet.
+    pub fn distributable(balance: U256, total_claimable: U256) -> U256 {
+        balance.saturating_sub(total_claimable)
+    }
+
+    /// Works out where `amount` goes without moving any funds.
+    ///
+    /// The global dev fee only applies if `global_dev_address` is set. `stake_target` is asked
+    /// for the address each reward distributor entry pays to; the zero address leaves that share
+    /// in the tax distributor. This is a plain computation, usable off-chain as well.
+    pub fn preview<F>(
+        &self,
+        amount: U256,
+        global_dev_address: Address,
+        global_dev_fee_per_100: U256,
+        dust_recipient: Address,
+        mut stake_target: F,
+    ) -> Result<DistributionPreview, Error>
+    where
+        F: FnMut(Address) -> Result<Address, Error>,
+    {
+        let global_dev_fee_per_100 = if global_dev_address == ZERO_ADDRESS {
+            U256::zero()
+        } else {
+            global_dev_fee_per_100
+        };
+        let split = self.split(amount, global_dev_fee_per_100)?;
+        let mut remainder = U256::zero();
+        let mut shares = Vec::with_capacity(self.0.len());
+        for (entry, share) in self.0.iter().zip(split.shares) {
+            let recipient = match entry.kind {
+                RecipientKind::Transfer => entry.target,
+                RecipientKind::Burn => entry.target,
+                RecipientKind::RewardDistributor if share.is_zero() => ZERO_ADDRESS,
+                RecipientKind::RewardDistributor => {
+                    let recipient = stake_target(entry.target)?;
+                    if recipient == ZERO_ADDRESS {
+                        remainder = remainder.checked_add(share).ok_or(Error::Overflow)?;
+                    }
+                    recipient
+                }
+            };
+            shares.push(PreviewShare {
+                kind: entry.kind,
+                recipient,
+                amount: share,
+            });
+        }
+        if dust_recipient == ZERO_ADDRESS {
+            remainder = remainder.checked_add(split.dust).ok_or(Error::Overflow)?;
+        }
+        Ok(DistributionPreview {
+            global_dev_address,
+            global_dev: split.global_dev,
+            shares,
+            dust_recipient,
+            dust: split.dust,
+            remainder,
+        })
+    }
+}
+
+/// An amount split by [`Distribution::split`].
+///
+/// `global_dev + shares.sum() + dust` equals the split amount.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Split {
+    /// Global dev fee.
+    pub global_dev: U256,
+    /// Share of each distribution entry, in entry order.
+    pub shares: Vec<U256>,
+    /// Rounding dust left over by the shares.
+    pub dust: U256,
+}
+
+/// Returns `amount * percent / 100`.
+pub(crate) fn percent_of(amount: U256, percent: U256) -> Result<U256, Error> {
+    amount
+        .checked_mul(percent)
+        .map(|product| product / U256::from(100))
+        .ok_or(Error::Overflow)
+}
+
+impl CLTyped for Distribution {
+    fn cl_type() -> CLType {
+        Vec::<DistributionEntry>::cl_type()
+    }
+}
+
+impl ToBytes for Distribution {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+}
+
+impl FromBytes for Distribution {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entries, remainder) = Vec::<DistributionEntry>::from_bytes(bytes)?;
+        Ok((Distribution(entries), remainder))
+    }
+}
+
+/// Where one distribution entry's share goes, as worked out by [`Distribution::preview`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct PreviewShare {
+    /// Kind of the entry.
+    pub kind: RecipientKind,
+    /// Transfer target, burn address, stake address picked by the reward distributor, or the
+    /// zero address for shares burned through `burn` and unrouted stake shares.
+    pub recipient: Address,
+    /// Share of the entry.
+    pub amount: U256,
+}
+
+/// Breakdown of what `distribute_tax` would do with an amount.
+///
+/// Serialized as `((Key, U256), List<(u8, Key, U256)>, (Key, U256, U256))`: the global dev
+/// address and fee, the shares, and the dust recipient, dust and remainder.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DistributionPreview {
+    /// Address receiving the global dev fee.
+    pub global_dev_address: Address,
+    /// Global dev fee.
+    pub global_dev: U256,
+    /// Share of each distribution entry, in entry order.
+    pub shares: Vec<PreviewShare>,
+    /// Address receiving the rounding dust, or the zero address if it is kept.
+    pub dust_recipient: Address,
+    /// Rounding dust.
+    pub dust: U256,
+    /// Amount staying in the tax distributor: kept dust and stake shares without a target.
+    pub remainder: U256,
+}
+
+type PreviewTuple = (
+    (Address, U256),
+    Vec<(u8, Address, U256)>,
+    (Address, U256, U256),
+);
+
+impl DistributionPreview {
+    fn to_tuple(&self) -> PreviewTuple {
+        (
+            (self.global_dev_address, self.global_dev),
+            self.shares
+                .iter()
+                .map(|share| (share.kind as u8, share.recipient, share.amount))
+                .collect(),
+            (self.dust_recipient, self.dust, self.remainder),
+        )
+    }
+}
+
+impl CLTyped for DistributionPreview {
+    fn cl_type() -> CLType {
+        PreviewTuple::cl_type()
+    }
+}
+
+impl ToBytes for DistributionPreview {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.to_tuple().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.to_tuple().serialized_length()
+    }
+}
+
+impl FromBytes for DistributionPreview {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (((global_dev_address, global_dev), shares, (dust_recipient, dust, remainder)), rem) =
+            PreviewTuple::from_bytes(bytes)?;
+        let shares = shares
+            .into_iter()
+            .map(|(kind, recipient, amount)| {
+                Ok(PreviewShare {
+                    kind: RecipientKind::from_u8(kind).ok_or(bytesrepr::Error::Formatting)?,
+                    recipient,
+                    amount,
+                })
+            })
+            .collect::<Result<_, bytesrepr::Error>>()?;
+        Ok((
+            DistributionPreview {
+                global_dev_address,
+                global_dev,
+                shares,
+                dust_recipient,
+                dust,
+                remainder,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use casper_types::{account::AccountHash, ContractPackageHash};
+
+    use super::*;
+    use crate::constants::MAX_GLOBAL_DEV_FEE_PER_100;
+
+    /// xorshift64*, so every case below replays exactly from its seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+
+        /// Small amounts, where rounding matters most, and amounts up to 2^128.
+        fn amount(&mut self) -> U256 {
+            match self.below(3) {
+                0 => U256::from(self.below(1_000)),
+                1 => U256::from(self.next()),
+                _ => U256::from(self.next()) * U256::from(self.next()),
+            }
+        }
+    }
+
+    fn account(i: u8) -> Address {
+        Address::from(AccountHash::new([i + 1; 32]))
+    }
+
+    fn package(i: u8) -> Address {
+        Address::from(ContractPackageHash::new([i + 1; 32]))
+    }
+
+    fn random_distribution(rng: &mut Rng) -> Distribution {
+        let len = 1 + rng.below(MAX_DISTRIBUTION_RECIPIENTS as u64) as usize;
+        let mut remaining = TOTAL_WEIGHT_BPS;
+        let entries = (0..len)
+            .map(|i| {
+                let weight_bps = if i + 1 == len {
+                    remaining
+                } else {
+                    rng.below(remaining as u64 + 1) as u16
+                };
+                remaining -= weight_bps;
+                let (kind, target) = match rng.below(3) {
+                    0 => (RecipientKind::Transfer, account(i as u8)),
+                    1 => (RecipientKind::Burn, ZERO_ADDRESS),
+                    _ => (RecipientKind::RewardDistributor, package(i as u8)),
+                };
+                DistributionEntry {
+                    kind,
+                    target,
+                    weight_bps,
+                }
+            })
+            .collect();
+        Distribution::new(entries).unwrap()
+    }
+
+    fn sum(amounts: impl Iterator<Item = U256>) -> U256 {
+        amounts.fold(U256::zero(), |total, amount| total + amount)
+    }
+
+    #[test]
+    fn split_conserves_random_amounts() {
+        for seed in 1..=2_000u64 {
+            let mut rng = Rng(seed);
+            let distribution = random_distribution(&mut rng);
+            let amount = rng.amount();
+            let fee = U256::from(rng.below(MAX_GLOBAL_DEV_FEE_PER_100 as u64 + 1));
+
+            let split = distribution.split(amount, fee).unwrap();
+            assert_eq!(
+                split.global_dev + sum(split.shares.iter().copied()) + split.dust,
+                amount,
+                "seed {}",
+                seed
+            );
+            // Each share rounds down by less than one unit.
+            assert!(
+                split.dust < U256::from(distribution.entries().len()),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn preview_routes_or_keeps_every_unit() {
+        for seed in 1..=2_000u64 {
+            let mut rng = Rng(seed);
+            let distribution = random_distribution(&mut rng);
+            let amount = rng.amount();
+            let global_dev_address = if rng.below(2) == 0 {
+                ZERO_ADDRESS
+            } else {
+                account(100)
+            };
+            let fee = U256::from(rng.below(MAX_GLOBAL_DEV_FEE_PER_100 as u64 + 1));
+            let dust_recipient = if rng.below(2) == 0 {
+                ZERO_ADDRESS
+            } else {
+                account(101)
+            };
+            let routed = rng.next();
+
+            let preview = distribution
+                .preview(amount, global_dev_address, fee, dust_recipient, |target| {
+                    // Reward distributors without a stake address leave their share here.
+                    let i = target.as_contract_package_hash().unwrap().value()[0];
+                    Ok(if routed & (1 << (i % 64)) != 0 {
+                        account(i + 50)
+                    } else {
+                        ZERO_ADDRESS
+                    })
+                })
+                .unwrap();
+
+            let sent = sum(preview
+                .shares
+                .iter()
+                .filter(|share| {
+                    share.kind != RecipientKind::RewardDistributor
+                        || share.recipient != ZERO_ADDRESS
+                })
+                .map(|share| share.amount));
+            let dust_sent = if dust_recipient == ZERO_ADDRESS {
+                U256::zero()
+            } else {
+                preview.dust
+            };
+            assert_eq!(
+                preview.global_dev + sent + dust_sent + preview.remainder,
+                amount,
+                "seed {}",
+                seed
+            );
+            if global_dev_address == ZERO_ADDRESS {
+                assert!(preview.global_dev.is_zero(), "seed {}", seed);
+            }
+        }
+    }
+
+    /// Owed amounts of a pull payments ledger, by recipient.
+    #[derive(Default)]
+    struct Credits(Vec<(Address, U256)>);
+
+    impl Credits {
+        fn credit(&mut self, recipient: Address, amount: U256) {
+            match self.0.iter_mut().find(|(owner, _)| *owner == recipient) {
+                Some((_, claimable)) => *claimable += amount,
+                None => self.0.push((recipient, amount)),
+            }
+        }
+
+        fn take(&mut self, i: usize) -> U256 {
+            core::mem::take(&mut self.0[i].1)
+        }
+
+        fn total(&self) -> U256 {
+            sum(self.0.iter().map(|(_, claimable)| *claimable))
+        }
+    }
+
+    #[test]
+    fn credited_funds_are_never_redistributed() {
+        for seed in 1..=500u64 {
+            let mut rng = Rng(seed);
+            let distribution = random_distribution(&mut rng);
+            let fee = U256::from(rng.below(MAX_GLOBAL_DEV_FEE_PER_100 as u64 + 1));
+            let dust_recipient = if rng.below(2) == 0 {
+                ZERO_ADDRESS
+            } else {
+                account(101)
+            };
+            let mut balance = U256::zero();
+            let mut credits = Credits::default();
+
+            for _ in 0..50 {
+                match rng.below(3) {
+                    0 => balance += rng.amount(),
+                    1 => {
+                        let amount = Distribution::distributable(balance, credits.total());
+                        let routed = rng.next();
+                        let preview = distribution
+                            .preview(amount, account(100), fee, dust_recipient, |target| {
+                                let i = target.as_contract_package_hash().unwrap().value()[0];
+                                Ok(if routed & (1 << (i % 64)) != 0 {
+                                    account(i + 50)
+                                } else {
+                                    ZERO_ADDRESS
+                                })
+                            })
+                            .unwrap();
+                        // Pull payments credit the global dev fee, transfer shares and dust;
+                        // burns and routed stake shares leave right away.
+                        credits.credit(preview.global_dev_address, preview.global_dev);
+                        for share in preview.shares {
+                            match share.kind {
+                                RecipientKind::Transfer => {
+                                    credits.credit(share.recipient, share.amount)
+                                }
+                                RecipientKind::RewardDistributor
+                                    if share.recipient == ZERO_ADDRESS => {}
+                                _ => balance -= share.amount,
+                            }
+                        }
+                        if dust_recipient != ZERO_ADDRESS {
+                            credits.credit(dust_recipient, preview.dust);
+                        }
+                        // Only the remainder is left to distribute next time.
+                        assert_eq!(
+                            Distribution::distributable(balance, credits.total()),
+                            preview.remainder,
+                            "seed {}",
+                            seed
+                        );
+                    }
+                    _ if !credits.0.is_empty() => {
+                        let i = rng.below(credits.0.len() as u64) as usize;
+                        balance -= credits.take(i);
+                    }
+                    _ => {}
+                }
+                // Every credit can still be claimed in full.
+                assert!(balance >= credits.total(), "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn weights_must_add_up() {
+        let entry = |weight_bps| DistributionEntry {
+            kind: RecipientKind::Burn,
+            target: ZERO_ADDRESS,
+            weight_bps,
+        };
+        assert!(Distribution::new(vec![]).is_err());
+        assert!(Distribution::new(vec![entry(5_000), entry(4_999)]).is_err());
+        assert!(Distribution::new(vec![entry(5_000), entry(5_000)]).is_ok());
+    }
+}
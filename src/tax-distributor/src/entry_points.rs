@@ -0,0 +1,237 @@
+//! Contains definition of the entry points.
+use alloc::{string::String, vec};
+
+use casper_types::{
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter, U256,
+};
+
+use casper_erc20::Address;
+
+use crate::{
+    constants::{
+        AMOUNT_RUNTIME_ARG_NAME, BURN_ADDRESS_RUNTIME_ARG_NAME, BURN_RUNTIME_ARG_NAME,
+        CLAIMABLE_ENTRY_POINT_NAME, CLAIM_ENTRY_POINT_NAME, DEV_ADDRESS_RUNTIME_ARG_NAME,
+        DEV_FEE_PER_100_RUNTIME_ARG_NAME, DEV_RUNTIME_ARG_NAME,
+        DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME, DISTRIBUTE_TAX_ENTRY_POINT_NAME,
+        DISTRIBUTION_RUNTIME_ARG_NAME, DUST_RECIPIENT_RUNTIME_ARG_NAME,
+        FUTURE_ADDRESS_RUNTIME_ARG_NAME, FUTURE_RUNTIME_ARG_NAME,
+        PREVIEW_DISTRIBUTION_ENTRY_POINT_NAME, PULL_PAYMENTS_RUNTIME_ARG_NAME,
+        RECIPIENT_RUNTIME_ARG_NAME, REWARD_DISTRIBUTOR_RUNTIME_ARG_NAME,
+        SET_BURN_ADDRESS_ENTRY_POINT_NAME, SET_DEFAULT_DISTRIBUTION_ENTRY_POINT_NAME,
+        SET_DEV_ADDRESS_ENTRY_POINT_NAME, SET_DISTRIBUTION_ENTRY_POINT_NAME,
+        SET_DUST_RECIPIENT_ENTRY_POINT_NAME, SET_FUTURE_ADDRESS_ENTRY_POINT_NAME,
+        SET_GLOBAL_DEV_ADDRESS_ENTRY_POINT_NAME, SET_PULL_PAYMENTS_ENTRY_POINT_NAME,
+        SET_REWARD_DISTRIBUTOR_ENTRY_POINT_NAME, STAKE_RUNTIME_ARG_NAME, TOKEN_RUNTIME_ARG_NAME,
+    },
+    Distribution, DistributionPreview,
+};
+
+/// Returns the `set_reward_distributor` entry point.
+pub fn set_reward_distributor() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_REWARD_DISTRIBUTOR_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(REWARD_DISTRIBUTOR_RUNTIME_ARG_NAME, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_dev_address` entry point.
+pub fn set_dev_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_DEV_ADDRESS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(DEV_ADDRESS_RUNTIME_ARG_NAME, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_global_dev_address` entry point.
+pub fn set_global_dev_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_GLOBAL_DEV_ADDRESS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(DEV_ADDRESS_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(DEV_FEE_PER_100_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_future_address` entry point.
+pub fn set_future_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_FUTURE_ADDRESS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(FUTURE_ADDRESS_RUNTIME_ARG_NAME, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_burn_address` entry point.
+pub fn set_burn_address() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_BURN_ADDRESS_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(BURN_ADDRESS_RUNTIME_ARG_NAME, Address::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_default_distribution` entry point.
+pub fn set_default_distribution() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_DEFAULT_DISTRIBUTION_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(STAKE_RUNTIME_ARG_NAME, u8::cl_type()),
+            Parameter::new(BURN_RUNTIME_ARG_NAME, u8::cl_type()),
+            Parameter::new(DEV_RUNTIME_ARG_NAME, u8::cl_type()),
+            Parameter::new(FUTURE_RUNTIME_ARG_NAME, u8::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_distribution` entry point.
+pub fn set_distribution() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_DISTRIBUTION_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(DISTRIBUTION_RUNTIME_ARG_NAME, Distribution::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_dust_recipient` entry point.
+pub fn set_dust_recipient() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_DUST_RECIPIENT_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            DUST_RECIPIENT_RUNTIME_ARG_NAME,
+            Address::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_pull_payments` entry point.
+pub fn set_pull_payments() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_PULL_PAYMENTS_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            PULL_PAYMENTS_RUNTIME_ARG_NAME,
+            bool::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `preview_distribution` entry point.
+pub fn preview_distribution() -> EntryPoint {
+    EntryPoint::new(
+        String::from(PREVIEW_DISTRIBUTION_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type()),
+        ],
+        DistributionPreview::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `distribute_tax` entry point.
+pub fn distribute_tax() -> EntryPoint {
+    EntryPoint::new(
+        String::from(DISTRIBUTE_TAX_ENTRY_POINT_NAME),
+        vec![Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type())],
+        bool::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `distributable_balance` entry point.
+pub fn distributable_balance() -> EntryPoint {
+    EntryPoint::new(
+        String::from(DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME),
+        vec![Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `claimable` entry point.
+pub fn claimable() -> EntryPoint {
+    EntryPoint::new(
+        String::from(CLAIMABLE_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(RECIPIENT_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type()),
+        ],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `claim` entry point.
+pub fn claim() -> EntryPoint {
+    EntryPoint::new(
+        String::from(CLAIM_ENTRY_POINT_NAME),
+        vec![Parameter::new(TOKEN_RUNTIME_ARG_NAME, Address::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the default set of tax distributor entry points.
+pub fn default() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(set_reward_distributor());
+    entry_points.add_entry_point(set_dev_address());
+    entry_points.add_entry_point(set_global_dev_address());
+    entry_points.add_entry_point(set_future_address());
+    entry_points.add_entry_point(set_burn_address());
+    entry_points.add_entry_point(set_default_distribution());
+    entry_points.add_entry_point(set_distribution());
+    entry_points.add_entry_point(set_dust_recipient());
+    entry_points.add_entry_point(set_pull_payments());
+    entry_points.add_entry_point(preview_distribution());
+    entry_points.add_entry_point(distribute_tax());
+    entry_points.add_entry_point(distributable_balance());
+    entry_points.add_entry_point(claimable());
+    entry_points.add_entry_point(claim());
+    entry_points
+}
@@ -0,0 +1,71 @@
+//! Error handling on the casper platform.
+use casper_types::ApiError;
+
+/// Errors which can be returned by the tax distributor.
+///
+/// When an `Error` is returned from a smart contract, it is converted to an [`ApiError::User`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Error {
+    /// Tax distributor called from within an invalid context.
+    InvalidContext,
+    /// Caller is not the owner of the tax distributor.
+    Unauthorized,
+    /// Token address is the zero address.
+    BadToken,
+    /// Reward distributor is not a contract, or does not return a distribution address.
+    BadRewardDistributor,
+    /// Global dev fee is out of range.
+    InvalidDevFee,
+    /// Distribution is empty, too long, or its weights do not add up.
+    InvalidDistribution,
+    /// No distribution has been configured for the token.
+    MissingDistribution,
+    /// Reward distributor failed to update rewards.
+    StakingFailed,
+    /// Operation would cause an integer overflow.
+    Overflow,
+    /// Transfer recipient of a distribution is the zero address.
+    BadRecipient,
+    /// Caller has no claimable balance of the token.
+    NothingToClaim,
+}
+
+const ERROR_INVALID_CONTEXT: u16 = 1;
+const ERROR_UNAUTHORIZED: u16 = 2;
+const ERROR_BAD_TOKEN: u16 = 3;
+const ERROR_BAD_REWARD_DISTRIBUTOR: u16 = 4;
+const ERROR_INVALID_DEV_FEE: u16 = 5;
+const ERROR_INVALID_DISTRIBUTION: u16 = 6;
+const ERROR_MISSING_DISTRIBUTION: u16 = 7;
+const ERROR_STAKING_FAILED: u16 = 8;
+const ERROR_OVERFLOW: u16 = 9;
+const ERROR_BAD_RECIPIENT: u16 = 10;
+const ERROR_NOTHING_TO_CLAIM: u16 = 11;
+
+impl From<casper_contract_utils::Error> for Error {
+    fn from(error: casper_contract_utils::Error) -> Self {
+        match error {
+            casper_contract_utils::Error::InvalidContext => Error::InvalidContext,
+            casper_contract_utils::Error::BadToken => Error::BadToken,
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::Unauthorized => ERROR_UNAUTHORIZED,
+            Error::BadToken => ERROR_BAD_TOKEN,
+            Error::BadRewardDistributor => ERROR_BAD_REWARD_DISTRIBUTOR,
+            Error::InvalidDevFee => ERROR_INVALID_DEV_FEE,
+            Error::InvalidDistribution => ERROR_INVALID_DISTRIBUTION,
+            Error::MissingDistribution => ERROR_MISSING_DISTRIBUTION,
+            Error::StakingFailed => ERROR_STAKING_FAILED,
+            Error::Overflow => ERROR_OVERFLOW,
+            Error::BadRecipient => ERROR_BAD_RECIPIENT,
+            Error::NothingToClaim => ERROR_NOTHING_TO_CLAIM,
+        };
+        ApiError::User(user_error)
+    }
+}
@@ -0,0 +1,602 @@
+//! On-chain tax distributor for ERC20 tokens on the Casper network.
+//!
+//! Collected token tax is split between a global dev fee and the per-token [`Distribution`], a
+//! weighted list of transfer, burn and reward distributor recipients. Reward distributor shares go
+//! to installed reward distributor contracts, referenced through [`RewardDistributor`].
+//!
+//! With pull payments enabled, the global dev fee, transfer shares and dust are credited to their
+//! recipients instead of transferred, and each recipient collects them with `claim(token)`. A
+//! recipient that reverts on receipt then cannot block the distribution for everybody else.
+//! Burns and reward distributor shares are still pushed, since reward distributors cannot claim.
+//! Credited amounts are left out of every later distribution until they are claimed.
+
+#![warn(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+pub mod constants;
+mod distribution;
+pub mod entry_points;
+mod error;
+mod reward_distributor;
+
+use alloc::{string::ToString, vec::Vec};
+
+use once_cell::unsync::OnceCell;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_contract_utils::{detail, token};
+use casper_erc20::Address;
+use casper_types::{contracts::NamedKeys, EntryPoints, Key, URef, U256};
+
+use constants::{
+    BURN_ADDRESSES_KEY_NAME, CLAIMABLE_KEY_NAME, DEV_ADDRESSES_KEY_NAME, DISTRIBUTIONS_KEY_NAME,
+    DUST_RECIPIENT_KEY_NAME, FUTURE_ADDRESSES_KEY_NAME, GLOBAL_DEV_ADDRESS_KEY_NAME,
+    GLOBAL_DEV_FEE_PER_100_KEY_NAME, MAX_GLOBAL_DEV_FEE_PER_100, OWNER_KEY_NAME,
+    PULL_PAYMENTS_KEY_NAME, REWARD_DISTRIBUTORS_KEY_NAME, TAX_DISTRIBUTOR_CONTRACT_KEY_NAME,
+    TOTAL_CLAIMABLE_KEY_NAME,
+};
+use detail::ZERO_ADDRESS;
+pub use distribution::{
+    Distribution, DistributionEntry, DistributionPreview, PreviewShare, RecipientKind, Split,
+};
+pub use error::Error;
+pub use reward_distributor::RewardDistributor;
+pub use token::Token;
+
+/// Implementation of the tax distributor contract.
+#[derive(Default)]
+pub struct TaxDistributor {
+    distributions_uref: OnceCell<URef>,
+    reward_distributors_uref: OnceCell<URef>,
+    dev_addresses_uref: OnceCell<URef>,
+    future_addresses_uref: OnceCell<URef>,
+    burn_addresses_uref: OnceCell<URef>,
+    claimable_uref: OnceCell<URef>,
+    total_claimable_uref: OnceCell<URef>,
+}
+
+impl TaxDistributor {
+    fn new(
+        distributions_uref: URef,
+        reward_distributors_uref: URef,
+        dev_addresses_uref: URef,
+        future_addresses_uref: URef,
+        burn_addresses_uref: URef,
+        claimable_uref: URef,
+        total_claimable_uref: URef,
+    ) -> Self {
+        Self {
+            distributions_uref: distributions_uref.into(),
+            reward_distributors_uref: reward_distributors_uref.into(),
+            dev_addresses_uref: dev_addresses_uref.into(),
+            future_addresses_uref: future_addresses_uref.into(),
+            burn_addresses_uref: burn_addresses_uref.into(),
+            claimable_uref: claimable_uref.into(),
+            total_claimable_uref: total_claimable_uref.into(),
+        }
+    }
+
+    fn distributions_uref(&self) -> URef {
+        *self
+            .distributions_uref
+            .get_or_init(|| detail::get_uref(DISTRIBUTIONS_KEY_NAME))
+    }
+
+    fn reward_distributors_uref(&self) -> URef {
+        *self
+            .reward_distributors_uref
+            .get_or_init(|| detail::get_uref(REWARD_DISTRIBUTORS_KEY_NAME))
+    }
+
+    fn dev_addresses_uref(&self) -> URef {
+        *self
+            .dev_addresses_uref
+            .get_or_init(|| detail::get_uref(DEV_ADDRESSES_KEY_NAME))
+    }
+
+    fn future_addresses_uref(&self) -> URef {
+        *self
+            .future_addresses_uref
+            .get_or_init(|| detail::get_uref(FUTURE_ADDRESSES_KEY_NAME))
+    }
+
+    fn burn_addresses_uref(&self) -> URef {
+        *self
+            .burn_addresses_uref
+            .get_or_init(|| detail::get_uref(BURN_ADDRESSES_KEY_NAME))
+    }
+
+    fn claimable_uref(&self) -> URef {
+        *self
+            .claimable_uref
+            .get_or_init(|| detail::get_uref(CLAIMABLE_KEY_NAME))
+    }
+
+    fn total_claimable_uref(&self) -> URef {
+        *self
+            .total_claimable_uref
+            .get_or_init(|| detail::get_uref(TOTAL_CLAIMABLE_KEY_NAME))
+    }
+
+    /// Reverts with [`Error::Unauthorized`] unless the direct caller is the owner.
+    fn only_owner(&self) -> Result<(), Error> {
+        let caller = detail::get_immediate_caller_address()?;
+        if caller != self.owner() {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Installs the tax distributor contract with the default set of entry points.
+    ///
+    /// This should be called from within `fn call()` of your contract. The installing account
+    /// becomes the owner allowed to call the setters.
+    pub fn install() -> Result<TaxDistributor, Error> {
+        let default_entry_points = entry_points::default();
+        TaxDistributor::install_custom(TAX_DISTRIBUTOR_CONTRACT_KEY_NAME, default_entry_points)
+    }
+
+    /// Returns the owner of the tax distributor.
+    pub fn owner(&self) -> Address {
+        detail::read_from(OWNER_KEY_NAME)
+    }
+
+    /// Returns the distribution configured for `token`.
+    pub fn distribution(&self, token: Address) -> Option<Distribution> {
+        detail::read_dictionary(self.distributions_uref(), token)
+    }
+
+    /// Returns the reward distributor configured for `token`.
+    pub fn reward_distributor(&self, token: Address) -> Option<RewardDistributor> {
+        detail::read_dictionary(self.reward_distributors_uref(), token)
+            .map(|address| RewardDistributor::from_address(address).unwrap_or_revert())
+    }
+
+    /// Returns the dev address of `token`, or the zero address if none is set.
+    pub fn dev_address(&self, token: Address) -> Address {
+        detail::read_dictionary(self.dev_addresses_uref(), token).unwrap_or(ZERO_ADDRESS)
+    }
+
+    /// Returns the future address of `token`, or the zero address if none is set.
+    pub fn future_address(&self, token: Address) -> Address {
+        detail::read_dictionary(self.future_addresses_uref(), token).unwrap_or(ZERO_ADDRESS)
+    }
+
+    /// Returns the burn address of `token`, or the zero address if burn shares call `burn`.
+    pub fn burn_address(&self, token: Address) -> Address {
+        detail::read_dictionary(self.burn_addresses_uref(), token).unwrap_or(ZERO_ADDRESS)
+    }
+
+    /// Returns the address receiving the global dev fee.
+    pub fn global_dev_address(&self) -> Address {
+        detail::read_from(GLOBAL_DEV_ADDRESS_KEY_NAME)
+    }
+
+    /// Returns the global dev fee, in percent of every distributed amount.
+    pub fn global_dev_fee_per_100(&self) -> U256 {
+        detail::read_from(GLOBAL_DEV_FEE_PER_100_KEY_NAME)
+    }
+
+    /// Returns the address receiving rounding dust, or the zero address if dust is kept.
+    pub fn dust_recipient(&self) -> Address {
+        detail::read_from(DUST_RECIPIENT_KEY_NAME)
+    }
+
+    /// Returns whether payouts are credited for claiming instead of transferred.
+    pub fn pull_payments(&self) -> bool {
+        detail::read_from(PULL_PAYMENTS_KEY_NAME)
+    }
+
+    /// Returns the amount of `token` that `recipient` can claim.
+    pub fn claimable(&self, recipient: Address, token: Address) -> U256 {
+        detail::read_pair_dictionary(self.claimable_uref(), recipient, token).unwrap_or_default()
+    }
+
+    /// Returns the amount of `token` owed to all recipients, which is excluded from distribution.
+    pub fn total_claimable(&self, token: Address) -> U256 {
+        detail::read_dictionary(self.total_claimable_uref(), token).unwrap_or_default()
+    }
+
+    /// Returns the amount of `token` that [`distribute_tax`](Self::distribute_tax) would split:
+    /// the balance left after amounts waiting to be claimed, or zero if `token` has no
+    /// distribution.
+    pub fn distributable_balance(&self, token: Address) -> Result<U256, Error> {
+        if self.distribution(token).is_none() {
+            return Ok(U256::zero());
+        }
+        let erc20 = Token::from_address(token)?;
+        Ok(Distribution::distributable(
+            erc20.balance_of(token::self_address()),
+            self.total_claimable(token),
+        ))
+    }
+
+    /// Sets the reward distributor receiving the stake share of `token`.
+    ///
+    /// The reward distributor must already return a distribution address for the caller. Only
+    /// its read-only `get_distribution_address` view is asked, so setting it rolls nothing.
+    pub fn set_reward_distributor(
+        &mut self,
+        token: Address,
+        reward_distributor: Address,
+    ) -> Result<(), Error> {
+        self.only_owner()?;
+        if token == ZERO_ADDRESS {
+            return Err(Error::BadToken);
+        }
+        let sender = detail::get_immediate_caller_address()?;
+        let reward_distributor = RewardDistributor::from_address(reward_distributor)?;
+        if reward_distributor.distribution_address(sender) == ZERO_ADDRESS {
+            return Err(Error::BadRewardDistributor);
+        }
+        detail::write_dictionary(
+            self.reward_distributors_uref(),
+            token,
+            reward_distributor.address(),
+        );
+        Ok(())
+    }
+
+    /// Sets the dev address of `token`. The zero address is allowed.
+    pub fn set_dev_address(&mut self, token: Address, dev_address: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        if token == ZERO_ADDRESS {
+            return Err(Error::BadToken);
+        }
+        detail::write_dictionary(self.dev_addresses_uref(), token, dev_address);
+        Ok(())
+    }
+
+    /// Sets the address and percentage of the global dev fee.
+    pub fn set_global_dev_address(
+        &mut self,
+        dev_address: Address,
+        dev_fee_per_100: U256,
+    ) -> Result<(), Error> {
+        self.only_owner()?;
+        if dev_fee_per_100 > U256::from(MAX_GLOBAL_DEV_FEE_PER_100) {
+            return Err(Error::InvalidDevFee);
+        }
+        detail::write_to(GLOBAL_DEV_ADDRESS_KEY_NAME, dev_address);
+        detail::write_to(GLOBAL_DEV_FEE_PER_100_KEY_NAME, dev_fee_per_100);
+        Ok(())
+    }
+
+    /// Sets the address receiving rounding dust. The zero address keeps dust in the contract.
+    pub fn set_dust_recipient(&mut self, dust_recipient: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        detail::write_to(DUST_RECIPIENT_KEY_NAME, dust_recipient);
+        Ok(())
+    }
+
+    /// Switches between crediting payouts for claiming and transferring them right away.
+    ///
+    /// Balances credited before switching back to push payments stay claimable. Burns and reward
+    /// distributor shares are pushed either way.
+    pub fn set_pull_payments(&mut self, pull_payments: bool) -> Result<(), Error> {
+        self.only_owner()?;
+        detail::write_to(PULL_PAYMENTS_KEY_NAME, pull_payments);
+        Ok(())
+    }
+
+    /// Sets the future address of `token`.
+    pub fn set_future_address(
+        &mut self,
+        token: Address,
+        future_address: Address,
+    ) -> Result<(), Error> {
+        self.only_owner()?;
+        if token == ZERO_ADDRESS {
+            return Err(Error::BadToken);
+        }
+        detail::write_dictionary(self.future_addresses_uref(), token, future_address);
+        Ok(())
+    }
+
+    /// Sets the address receiving the burn share of `token` in place of a `burn` call.
+    ///
+    /// Tokens without a `burn` entry point, such as plain casper-erc20 tokens, need one, or every
+    /// distribution with a burn share reverts. The zero address goes back to calling `burn`.
+    pub fn set_burn_address(&mut self, token: Address, burn_address: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        if token == ZERO_ADDRESS {
+            return Err(Error::BadToken);
+        }
+        detail::write_dictionary(self.burn_addresses_uref(), token, burn_address);
+        Ok(())
+    }
+
+    /// Sets the weighted recipients of `token`'s tax. The weights must add up to 10000.
+    pub fn set_distribution(
+        &mut self,
+        token: Address,
+        entries: Vec<DistributionEntry>,
+    ) -> Result<(), Error> {
+        self.only_owner()?;
+        if token == ZERO_ADDRESS {
+            return Err(Error::BadToken);
+        }
+        let distribution = Distribution::new(entries)?;
+        detail::write_dictionary(self.distributions_uref(), token, distribution);
+        Ok(())
+    }
+
+    /// Sets the distribution of `token` from stake, burn, dev and future percentages, which must
+    /// add up to 100.
+    ///
+    /// The current dev, future, burn and reward distributor addresses of `token` are copied into
+    /// the distribution, so changing them later requires calling this again. Dev and future shares
+    /// whose address is unset roll into the stake share.
+    pub fn set_default_distribution(
+        &mut self,
+        token: Address,
+        stake: u8,
+        burn: u8,
+        dev: u8,
+        future: u8,
+    ) -> Result<(), Error> {
+        let total = stake as u16 + burn as u16 + dev as u16 + future as u16;
+        if total != 100 {
+            return Err(Error::InvalidDistribution);
+        }
+        let mut stake_weight = stake as u16 * 100;
+        let mut entries = Vec::new();
+        if burn != 0 {
+            entries.push(DistributionEntry {
+                kind: RecipientKind::Burn,
+                target: self.burn_address(token),
+                weight_bps: burn as u16 * 100,
+            });
+        }
+        for (share, recipient) in [
+            (dev, self.dev_address(token)),
+            (future, self.future_address(token)),
+        ] {
+            if share == 0 {
+                continue;
+            }
+            if recipient == ZERO_ADDRESS {
+                stake_weight += share as u16 * 100;
+                continue;
+            }
+            entries.push(DistributionEntry {
+                kind: RecipientKind::Transfer,
+                target: recipient,
+                weight_bps: share as u16 * 100,
+            });
+        }
+        if stake_weight != 0 {
+            let reward_distributor = self
+                .reward_distributor(token)
+                .ok_or(Error::BadRewardDistributor)?;
+            entries.push(DistributionEntry {
+                kind: RecipientKind::RewardDistributor,
+                target: reward_distributor.address(),
+                weight_bps: stake_weight,
+            });
+        }
+        self.set_distribution(token, entries)
+    }
+
+    /// Returns what [`distribute_tax`](Self::distribute_tax) would do with `amount` of `token`,
+    /// without moving funds.
+    ///
+    /// `amount` is capped at the [distributable balance](Self::distributable_balance), so amounts
+    /// waiting to be claimed are never previewed as distributed.
+    ///
+    /// Stake targets come from the reward distributors' `get_distribution_address` view, so they
+    /// are not rolled.
+    pub fn preview_distribution(
+        &self,
+        token: Address,
+        amount: U256,
+    ) -> Result<DistributionPreview, Error> {
+        let sender = detail::get_immediate_caller_address()?;
+        let dist = self.distribution(token).ok_or(Error::MissingDistribution)?;
+        let amount = amount.min(self.distributable_balance(token)?);
+        dist.preview(
+            amount,
+            self.global_dev_address(),
+            self.global_dev_fee_per_100(),
+            self.dust_recipient(),
+            |reward_distributor| {
+                Ok(RewardDistributor::from_address(reward_distributor)?
+                    .distribution_address(sender))
+            },
+        )
+    }
+
+    /// Distributes the tax distributor's balance of `token` according to its distribution.
+    ///
+    /// Amounts still waiting to be claimed are left out. The rest is split as shown by
+    /// [`preview_distribution`](Self::preview_distribution), except that stake targets are rolled.
+    /// The global dev fee, the shares and the rounding dust add up to that amount exactly. Dust
+    /// goes to the dust recipient, or stays here for the next call if none is set. Shares of
+    /// reward distributors that return no distribution address also stay here.
+    ///
+    /// With pull payments enabled the global dev fee, transfer shares and dust are credited to
+    /// their recipients. Burns and reward distributor shares are still settled right away:
+    /// reward distributors book whatever they hold in the same `update_rewards` call and have no
+    /// way to claim, so a credited stake share would never reach stakers.
+    pub fn distribute_tax(&mut self, token: Address) -> Result<bool, Error> {
+        let sender = detail::get_immediate_caller_address()?;
+        let dist = self.distribution(token).ok_or(Error::MissingDistribution)?;
+        let erc20 = Token::from_address(token)?;
+        let amount = self.distributable_balance(token)?;
+        if amount.is_zero() {
+            return Ok(false);
+        }
+        let preview = dist.preview(
+            amount,
+            self.global_dev_address(),
+            self.global_dev_fee_per_100(),
+            self.dust_recipient(),
+            |reward_distributor| {
+                Ok(RewardDistributor::from_address(reward_distributor)?
+                    .roll_and_get_distribution_address(sender))
+            },
+        )?;
+
+        let pull_payments = self.pull_payments();
+
+        if !preview.global_dev.is_zero() {
+            self.pay(
+                &erc20,
+                preview.global_dev_address,
+                preview.global_dev,
+                pull_payments,
+            )?;
+        }
+
+        for (entry, share) in dist.entries().iter().zip(preview.shares) {
+            if share.amount.is_zero() {
+                continue;
+            }
+            match entry.kind {
+                RecipientKind::Transfer => {
+                    self.pay(&erc20, share.recipient, share.amount, pull_payments)?
+                }
+                RecipientKind::Burn if share.recipient == ZERO_ADDRESS => erc20.burn(share.amount),
+                RecipientKind::Burn => erc20.transfer(share.recipient, share.amount),
+                RecipientKind::RewardDistributor => {
+                    if share.recipient == ZERO_ADDRESS {
+                        continue;
+                    }
+                    // Pushed even with pull payments, see above.
+                    erc20.transfer(share.recipient, share.amount);
+                    let reward_distributor = RewardDistributor::from_address(entry.target)?;
+                    if !reward_distributor.update_rewards(share.recipient) {
+                        return Err(Error::StakingFailed);
+                    }
+                }
+            }
+        }
+
+        if !preview.dust.is_zero() && preview.dust_recipient != ZERO_ADDRESS {
+            self.pay(&erc20, preview.dust_recipient, preview.dust, pull_payments)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Transfers everything the caller can claim of `token` to the caller, and returns the amount.
+    pub fn claim(&mut self, token: Address) -> Result<U256, Error> {
+        let recipient = detail::get_immediate_caller_address()?;
+        let erc20 = Token::from_address(token)?;
+        let amount = self.claimable(recipient, token);
+        if amount.is_zero() {
+            return Err(Error::NothingToClaim);
+        }
+        let total_claimable = self
+            .total_claimable(token)
+            .checked_sub(amount)
+            .ok_or(Error::Overflow)?;
+
+        detail::write_pair_dictionary(self.claimable_uref(), recipient, token, U256::zero());
+        detail::write_dictionary(self.total_claimable_uref(), token, total_claimable);
+        erc20.transfer(recipient, amount);
+        Ok(amount)
+    }
+
+    /// Sends `amount` of `erc20` to `recipient`, or credits it for claiming if `pull_payments`.
+    fn pay(
+        &mut self,
+        erc20: &Token,
+        recipient: Address,
+        amount: U256,
+        pull_payments: bool,
+    ) -> Result<(), Error> {
+        if !pull_payments {
+            erc20.transfer(recipient, amount);
+            return Ok(());
+        }
+        let token = erc20.address();
+        let claimable = self
+            .claimable(recipient, token)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        let total_claimable = self
+            .total_claimable(token)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        detail::write_pair_dictionary(self.claimable_uref(), recipient, token, claimable);
+        detail::write_dictionary(self.total_claimable_uref(), token, total_claimable);
+        Ok(())
+    }
+
+    /// Installs the tax distributor contract with a custom set of entry points.
+    #[doc(hidden)]
+    pub fn install_custom(
+        contract_key_name: &str,
+        entry_points: EntryPoints,
+    ) -> Result<TaxDistributor, Error> {
+        let distributions_uref = storage::new_dictionary(DISTRIBUTIONS_KEY_NAME).unwrap_or_revert();
+        let reward_distributors_uref =
+            storage::new_dictionary(REWARD_DISTRIBUTORS_KEY_NAME).unwrap_or_revert();
+        let dev_addresses_uref = storage::new_dictionary(DEV_ADDRESSES_KEY_NAME).unwrap_or_revert();
+        let future_addresses_uref =
+            storage::new_dictionary(FUTURE_ADDRESSES_KEY_NAME).unwrap_or_revert();
+        let burn_addresses_uref =
+            storage::new_dictionary(BURN_ADDRESSES_KEY_NAME).unwrap_or_revert();
+        let claimable_uref = storage::new_dictionary(CLAIMABLE_KEY_NAME).unwrap_or_revert();
+        let total_claimable_uref =
+            storage::new_dictionary(TOTAL_CLAIMABLE_KEY_NAME).unwrap_or_revert();
+
+        let owner = detail::get_caller_address()?;
+
+        let mut named_keys = NamedKeys::new();
+
+        let owner_key = Key::from(storage::new_uref(owner).into_read());
+        let global_dev_address_key = Key::from(storage::new_uref(ZERO_ADDRESS).into_read_write());
+        let global_dev_fee_per_100_key =
+            Key::from(storage::new_uref(U256::zero()).into_read_write());
+        let dust_recipient_key = Key::from(storage::new_uref(ZERO_ADDRESS).into_read_write());
+        let pull_payments_key = Key::from(storage::new_uref(false).into_read_write());
+
+        for (name, uref) in [
+            (DISTRIBUTIONS_KEY_NAME, distributions_uref),
+            (REWARD_DISTRIBUTORS_KEY_NAME, reward_distributors_uref),
+            (DEV_ADDRESSES_KEY_NAME, dev_addresses_uref),
+            (FUTURE_ADDRESSES_KEY_NAME, future_addresses_uref),
+            (BURN_ADDRESSES_KEY_NAME, burn_addresses_uref),
+            (CLAIMABLE_KEY_NAME, claimable_uref),
+            (TOTAL_CLAIMABLE_KEY_NAME, total_claimable_uref),
+        ] {
+            runtime::remove_key(name);
+            named_keys.insert(name.to_string(), Key::from(uref));
+        }
+
+        named_keys.insert(OWNER_KEY_NAME.to_string(), owner_key);
+        named_keys.insert(
+            GLOBAL_DEV_ADDRESS_KEY_NAME.to_string(),
+            global_dev_address_key,
+        );
+        named_keys.insert(
+            GLOBAL_DEV_FEE_PER_100_KEY_NAME.to_string(),
+            global_dev_fee_per_100_key,
+        );
+        named_keys.insert(DUST_RECIPIENT_KEY_NAME.to_string(), dust_recipient_key);
+        named_keys.insert(PULL_PAYMENTS_KEY_NAME.to_string(), pull_payments_key);
+
+        let (contract_hash, _version) =
+            storage::new_locked_contract(entry_points, Some(named_keys), None, None);
+
+        // Hash of the installed contract will be reachable through named keys.
+        runtime::put_key(contract_key_name, Key::from(contract_hash));
+
+        Ok(TaxDistributor::new(
+            distributions_uref,
+            reward_distributors_uref,
+            dev_addresses_uref,
+            future_addresses_uref,
+            burn_addresses_uref,
+            claimable_uref,
+            total_claimable_uref,
+        ))
+    }
+}
@@ -0,0 +1,70 @@
+//! Cross-contract reference to an installed reward distributor.
+use casper_contract::contract_api::runtime;
+use casper_erc20::Address;
+use casper_types::{runtime_args, ContractPackageHash, RuntimeArgs};
+
+use crate::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+        ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME, SENDER_RUNTIME_ARG_NAME,
+        UPDATE_REWARDS_ENTRY_POINT_NAME,
+    },
+    error::Error,
+};
+
+/// Reward distributor contract package, called through its
+/// `roll_and_get_distribution_address(sender)` and `update_rewards(address)` entry points, and
+/// the `get_distribution_address(sender)` view used for previews.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RewardDistributor(ContractPackageHash);
+
+impl RewardDistributor {
+    /// Returns the reward distributor installed as the contract package at `address`.
+    pub fn from_address(address: Address) -> Result<Self, Error> {
+        address
+            .as_contract_package_hash()
+            .map(|contract_package_hash| RewardDistributor(*contract_package_hash))
+            .ok_or(Error::BadRewardDistributor)
+    }
+
+    /// Returns the address of the reward distributor contract package.
+    pub fn address(&self) -> Address {
+        Address::from(self.0)
+    }
+
+    /// Returns the address the next batch of staking rewards should be sent to.
+    pub fn roll_and_get_distribution_address(&self, sender: Address) -> Address {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            ROLL_AND_GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+            runtime_args! {
+                SENDER_RUNTIME_ARG_NAME => sender,
+            },
+        )
+    }
+
+    /// Returns the address `roll_and_get_distribution_address` would return, without rolling.
+    pub fn distribution_address(&self, sender: Address) -> Address {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            GET_DISTRIBUTION_ADDRESS_ENTRY_POINT_NAME,
+            runtime_args! {
+                SENDER_RUNTIME_ARG_NAME => sender,
+            },
+        )
+    }
+
+    /// Notifies the reward distributor that rewards were sent to `address`.
+    pub fn update_rewards(&self, address: Address) -> bool {
+        runtime::call_versioned_contract(
+            self.0,
+            None,
+            UPDATE_REWARDS_ENTRY_POINT_NAME,
+            runtime_args! {
+                ADDRESS_RUNTIME_ARG_NAME => address,
+            },
+        )
+    }
+}
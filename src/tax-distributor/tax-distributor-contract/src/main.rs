@@ -0,0 +1,168 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20::Address;
+use casper_tax_distributor::{
+    constants::{
+        AMOUNT_RUNTIME_ARG_NAME, BURN_ADDRESS_RUNTIME_ARG_NAME, BURN_RUNTIME_ARG_NAME,
+        DEV_ADDRESS_RUNTIME_ARG_NAME, DEV_FEE_PER_100_RUNTIME_ARG_NAME, DEV_RUNTIME_ARG_NAME,
+        DISTRIBUTION_RUNTIME_ARG_NAME, DUST_RECIPIENT_RUNTIME_ARG_NAME,
+        FUTURE_ADDRESS_RUNTIME_ARG_NAME, FUTURE_RUNTIME_ARG_NAME, PULL_PAYMENTS_RUNTIME_ARG_NAME,
+        RECIPIENT_RUNTIME_ARG_NAME, REWARD_DISTRIBUTOR_RUNTIME_ARG_NAME, STAKE_RUNTIME_ARG_NAME,
+        TOKEN_RUNTIME_ARG_NAME,
+    },
+    DistributionEntry, TaxDistributor,
+};
+use casper_types::{CLValue, U256};
+
+#[no_mangle]
+pub extern "C" fn set_reward_distributor() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let reward_distributor: Address = runtime::get_named_arg(REWARD_DISTRIBUTOR_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_reward_distributor(token, reward_distributor)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_dev_address() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let dev_address: Address = runtime::get_named_arg(DEV_ADDRESS_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_dev_address(token, dev_address)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_global_dev_address() {
+    let dev_address: Address = runtime::get_named_arg(DEV_ADDRESS_RUNTIME_ARG_NAME);
+    let dev_fee_per_100: U256 = runtime::get_named_arg(DEV_FEE_PER_100_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_global_dev_address(dev_address, dev_fee_per_100)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_future_address() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let future_address: Address = runtime::get_named_arg(FUTURE_ADDRESS_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_future_address(token, future_address)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_burn_address() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let burn_address: Address = runtime::get_named_arg(BURN_ADDRESS_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_burn_address(token, burn_address)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_default_distribution() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let stake: u8 = runtime::get_named_arg(STAKE_RUNTIME_ARG_NAME);
+    let burn: u8 = runtime::get_named_arg(BURN_RUNTIME_ARG_NAME);
+    let dev: u8 = runtime::get_named_arg(DEV_RUNTIME_ARG_NAME);
+    let future: u8 = runtime::get_named_arg(FUTURE_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_default_distribution(token, stake, burn, dev, future)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_distribution() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let entries: Vec<DistributionEntry> = runtime::get_named_arg(DISTRIBUTION_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_distribution(token, entries)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_dust_recipient() {
+    let dust_recipient: Address = runtime::get_named_arg(DUST_RECIPIENT_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_dust_recipient(dust_recipient)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_pull_payments() {
+    let pull_payments: bool = runtime::get_named_arg(PULL_PAYMENTS_RUNTIME_ARG_NAME);
+
+    TaxDistributor::default()
+        .set_pull_payments(pull_payments)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn preview_distribution() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    let preview = TaxDistributor::default()
+        .preview_distribution(token, amount)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(preview).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn distribute_tax() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+
+    let distributed = TaxDistributor::default()
+        .distribute_tax(token)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(distributed).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn distributable_balance() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+
+    let distributable_balance = TaxDistributor::default()
+        .distributable_balance(token)
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(distributable_balance).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn claimable() {
+    let recipient: Address = runtime::get_named_arg(RECIPIENT_RUNTIME_ARG_NAME);
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+
+    let claimable = TaxDistributor::default().claimable(recipient, token);
+    runtime::ret(CLValue::from_t(claimable).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn claim() {
+    let token: Address = runtime::get_named_arg(TOKEN_RUNTIME_ARG_NAME);
+
+    let claimed = TaxDistributor::default().claim(token).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(claimed).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn call() {
+    let _tax_distributor = TaxDistributor::install().unwrap_or_revert();
+}
@@ -0,0 +1,56 @@
+//! Constants used by the taxed ERC20 contract.
+
+/// Name of named-key for `owner`.
+pub const OWNER_KEY_NAME: &str = "owner";
+/// Name of named-key for `tax_per_mille`.
+pub const TAX_PER_MILLE_KEY_NAME: &str = "tax_per_mille";
+/// Name of named-key for `tax_distributor`.
+pub const TAX_DISTRIBUTOR_KEY_NAME: &str = "tax_distributor";
+/// Name of named-key for `distribution_threshold`.
+pub const DISTRIBUTION_THRESHOLD_KEY_NAME: &str = "distribution_threshold";
+/// Name of dictionary-key for `exemptions`.
+pub const EXEMPTIONS_KEY_NAME: &str = "exemptions";
+/// Name of named-key for `contract`.
+pub const TAXED_ERC20_TOKEN_CONTRACT_KEY_NAME: &str = "taxed_erc20_token_contract";
+
+/// Upper bound of `tax_per_mille`.
+pub const MAX_TAX_PER_MILLE: u16 = 1000;
+
+/// Name of `burn` entry point.
+pub const BURN_ENTRY_POINT_NAME: &str = "burn";
+/// Name of `tax_per_mille` entry point.
+pub const TAX_PER_MILLE_ENTRY_POINT_NAME: &str = "tax_per_mille";
+/// Name of `tax_distributor` entry point.
+pub const TAX_DISTRIBUTOR_ENTRY_POINT_NAME: &str = "tax_distributor";
+/// Name of `distribution_threshold` entry point.
+pub const DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME: &str = "distribution_threshold";
+/// Name of `exemption` entry point.
+pub const EXEMPTION_ENTRY_POINT_NAME: &str = "exemption";
+/// Name of `set_exemption` entry point.
+pub const SET_EXEMPTION_ENTRY_POINT_NAME: &str = "set_exemption";
+/// Name of `set_tax_per_mille` entry point.
+pub const SET_TAX_PER_MILLE_ENTRY_POINT_NAME: &str = "set_tax_per_mille";
+/// Name of `set_tax_distributor` entry point.
+pub const SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME: &str = "set_tax_distributor";
+/// Name of `set_distribution_threshold` entry point.
+pub const SET_DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME: &str = "set_distribution_threshold";
+
+/// Name of `distribute_tax` entry point of the tax distributor.
+pub const DISTRIBUTE_TAX_ENTRY_POINT_NAME: &str = "distribute_tax";
+/// Name of `distributable_balance` entry point of the tax distributor.
+pub const DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME: &str = "distributable_balance";
+
+/// Name of `tax_per_mille` runtime argument.
+pub const TAX_PER_MILLE_RUNTIME_ARG_NAME: &str = "tax_per_mille";
+/// Name of `tax_distributor` runtime argument.
+pub const TAX_DISTRIBUTOR_RUNTIME_ARG_NAME: &str = "tax_distributor";
+/// Name of `distribution_threshold` runtime argument.
+pub const DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME: &str = "distribution_threshold";
+/// Name of `address` runtime argument.
+pub const ADDRESS_RUNTIME_ARG_NAME: &str = "address";
+/// Name of `as_sender` runtime argument.
+pub const AS_SENDER_RUNTIME_ARG_NAME: &str = "as_sender";
+/// Name of `as_recipient` runtime argument.
+pub const AS_RECIPIENT_RUNTIME_ARG_NAME: &str = "as_recipient";
+/// Name of `token` runtime argument.
+pub const TOKEN_RUNTIME_ARG_NAME: &str = "token";
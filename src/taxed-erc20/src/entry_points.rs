@@ -0,0 +1,149 @@
+//! Contains definition of the entry points.
+use alloc::{string::String, vec, vec::Vec};
+
+use casper_types::{
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter, U256,
+};
+
+use casper_erc20::{constants::AMOUNT_RUNTIME_ARG_NAME, Address};
+
+use crate::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, AS_RECIPIENT_RUNTIME_ARG_NAME, AS_SENDER_RUNTIME_ARG_NAME,
+        BURN_ENTRY_POINT_NAME, DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME,
+        DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME, EXEMPTION_ENTRY_POINT_NAME,
+        SET_DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME, SET_EXEMPTION_ENTRY_POINT_NAME,
+        SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME, SET_TAX_PER_MILLE_ENTRY_POINT_NAME,
+        TAX_DISTRIBUTOR_ENTRY_POINT_NAME, TAX_DISTRIBUTOR_RUNTIME_ARG_NAME,
+        TAX_PER_MILLE_ENTRY_POINT_NAME, TAX_PER_MILLE_RUNTIME_ARG_NAME,
+    },
+    Exemption,
+};
+
+/// Returns the `burn` entry point.
+pub fn burn() -> EntryPoint {
+    EntryPoint::new(
+        String::from(BURN_ENTRY_POINT_NAME),
+        vec![Parameter::new(AMOUNT_RUNTIME_ARG_NAME, U256::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `tax_per_mille` entry point.
+pub fn tax_per_mille() -> EntryPoint {
+    EntryPoint::new(
+        String::from(TAX_PER_MILLE_ENTRY_POINT_NAME),
+        Vec::new(),
+        u16::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `tax_distributor` entry point.
+pub fn tax_distributor() -> EntryPoint {
+    EntryPoint::new(
+        String::from(TAX_DISTRIBUTOR_ENTRY_POINT_NAME),
+        Vec::new(),
+        Address::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `distribution_threshold` entry point.
+pub fn distribution_threshold() -> EntryPoint {
+    EntryPoint::new(
+        String::from(DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME),
+        Vec::new(),
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `exemption` entry point.
+pub fn exemption() -> EntryPoint {
+    EntryPoint::new(
+        String::from(EXEMPTION_ENTRY_POINT_NAME),
+        vec![Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type())],
+        Exemption::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_exemption` entry point.
+pub fn set_exemption() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_EXEMPTION_ENTRY_POINT_NAME),
+        vec![
+            Parameter::new(ADDRESS_RUNTIME_ARG_NAME, Address::cl_type()),
+            Parameter::new(AS_SENDER_RUNTIME_ARG_NAME, bool::cl_type()),
+            Parameter::new(AS_RECIPIENT_RUNTIME_ARG_NAME, bool::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_tax_per_mille` entry point.
+pub fn set_tax_per_mille() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_TAX_PER_MILLE_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            TAX_PER_MILLE_RUNTIME_ARG_NAME,
+            u16::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_tax_distributor` entry point.
+pub fn set_tax_distributor() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_TAX_DISTRIBUTOR_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            TAX_DISTRIBUTOR_RUNTIME_ARG_NAME,
+            Address::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the `set_distribution_threshold` entry point.
+pub fn set_distribution_threshold() -> EntryPoint {
+    EntryPoint::new(
+        String::from(SET_DISTRIBUTION_THRESHOLD_ENTRY_POINT_NAME),
+        vec![Parameter::new(
+            DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME,
+            U256::cl_type(),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Returns the default set of taxed ERC20 entry points: the ERC20 ones, `burn`, the tax settings
+/// and exemptions.
+pub fn default() -> EntryPoints {
+    let mut entry_points = casper_erc20::entry_points::default();
+    entry_points.add_entry_point(burn());
+    entry_points.add_entry_point(tax_per_mille());
+    entry_points.add_entry_point(tax_distributor());
+    entry_points.add_entry_point(distribution_threshold());
+    entry_points.add_entry_point(exemption());
+    entry_points.add_entry_point(set_exemption());
+    entry_points.add_entry_point(set_tax_per_mille());
+    entry_points.add_entry_point(set_tax_distributor());
+    entry_points.add_entry_point(set_distribution_threshold());
+    entry_points
+}
@@ -0,0 +1,52 @@
+//! Error handling on the casper platform.
+use casper_types::ApiError;
+
+/// Errors which can be returned by the taxed ERC20 token.
+///
+/// When an `Error` is returned from a smart contract, it is converted to an [`ApiError::User`].
+/// Errors of the underlying ERC20 implementation keep their codes.
+pub enum Error {
+    /// Taxed ERC20 called from within an invalid context.
+    InvalidContext,
+    /// Caller is not the owner of the token.
+    Unauthorized,
+    /// Tax is out of range.
+    InvalidTax,
+    /// Tax distributor is not a contract.
+    BadTaxDistributor,
+    /// Error of the underlying ERC20 implementation.
+    ERC20(casper_erc20::Error),
+}
+
+const ERROR_INVALID_CONTEXT: u16 = 1;
+const ERROR_UNAUTHORIZED: u16 = 2;
+const ERROR_INVALID_TAX: u16 = 3;
+const ERROR_BAD_TAX_DISTRIBUTOR: u16 = 4;
+
+impl From<casper_erc20::Error> for Error {
+    fn from(error: casper_erc20::Error) -> Self {
+        Error::ERC20(error)
+    }
+}
+
+impl From<casper_contract_utils::Error> for Error {
+    fn from(error: casper_contract_utils::Error) -> Self {
+        match error {
+            casper_contract_utils::Error::InvalidContext => Error::InvalidContext,
+            casper_contract_utils::Error::BadToken => Error::BadTaxDistributor,
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let user_error = match error {
+            Error::InvalidContext => ERROR_INVALID_CONTEXT,
+            Error::Unauthorized => ERROR_UNAUTHORIZED,
+            Error::InvalidTax => ERROR_INVALID_TAX,
+            Error::BadTaxDistributor => ERROR_BAD_TAX_DISTRIBUTOR,
+            Error::ERC20(error) => return ApiError::from(error),
+        };
+        ApiError::User(user_error)
+    }
+}
@@ -0,0 +1,47 @@
+//! Tax exemptions of addresses.
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Whether transfers from or to an address are exempt from the tax.
+///
+/// Serialized as a `(bool, bool)` tuple of `as_sender` and `as_recipient`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Exemption {
+    /// Transfers from the address are not taxed.
+    pub as_sender: bool,
+    /// Transfers to the address are not taxed.
+    pub as_recipient: bool,
+}
+
+impl CLTyped for Exemption {
+    fn cl_type() -> CLType {
+        <(bool, bool)>::cl_type()
+    }
+}
+
+impl ToBytes for Exemption {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (self.as_sender, self.as_recipient).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        (self.as_sender, self.as_recipient).serialized_length()
+    }
+}
+
+impl FromBytes for Exemption {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let ((as_sender, as_recipient), remainder) = <(bool, bool)>::from_bytes(bytes)?;
+        Ok((
+            Exemption {
+                as_sender,
+                as_recipient,
+            },
+            remainder,
+        ))
+    }
+}
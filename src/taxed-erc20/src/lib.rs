@@ -0,0 +1,263 @@
+//! ERC20 token charging a transfer tax for the Casper network.
+//!
+//! [`TaxedERC20`] extends [`ERC20`] through its [`TransferHook`](casper_erc20::TransferHook):
+//! `tax_per_mille` of every `transfer` and `transfer_from` is credited to a tax distributor
+//! contract, which is asked to `distribute_tax` once its distributable balance reaches the
+//! distribution threshold. Addresses can be exempted from the tax as sender, as recipient, or
+//! both. The token also exposes `burn(amount)`, which the tax distributor uses for burn shares.
+
+#![warn(missing_docs)]
+#![no_std]
+
+extern crate alloc;
+
+pub mod constants;
+pub mod entry_points;
+mod error;
+mod exemption;
+mod tax;
+
+use alloc::string::{String, ToString};
+
+use once_cell::unsync::OnceCell;
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_contract_utils::detail;
+use casper_erc20::{Address, ERC20};
+use casper_types::{contracts::NamedKeys, EntryPoints, Key, URef, U256};
+
+use constants::{
+    DISTRIBUTION_THRESHOLD_KEY_NAME, EXEMPTIONS_KEY_NAME, MAX_TAX_PER_MILLE, OWNER_KEY_NAME,
+    TAXED_ERC20_TOKEN_CONTRACT_KEY_NAME, TAX_DISTRIBUTOR_KEY_NAME, TAX_PER_MILLE_KEY_NAME,
+};
+pub use error::Error;
+pub use exemption::Exemption;
+use tax::TaxHook;
+
+/// Implementation of the taxed ERC20 token.
+#[derive(Default)]
+pub struct TaxedERC20 {
+    erc20: ERC20,
+    exemptions_uref: OnceCell<URef>,
+}
+
+impl TaxedERC20 {
+    fn new(erc20: ERC20, exemptions_uref: URef) -> Self {
+        Self {
+            erc20,
+            exemptions_uref: exemptions_uref.into(),
+        }
+    }
+
+    fn exemptions_uref(&self) -> URef {
+        *self
+            .exemptions_uref
+            .get_or_init(|| detail::get_uref(EXEMPTIONS_KEY_NAME))
+    }
+
+    /// Reverts with [`Error::Unauthorized`] unless the direct caller is the owner.
+    fn only_owner(&self) -> Result<(), Error> {
+        let caller = detail::get_immediate_caller_address()?;
+        if caller != self.owner() {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn tax_hook(&self) -> Result<TaxHook, Error> {
+        Ok(TaxHook {
+            token: detail::get_caller_address()?,
+            tax_per_mille: self.tax_per_mille(),
+            tax_distributor: self.tax_distributor(),
+            distribution_threshold: self.distribution_threshold(),
+            exemptions_uref: self.exemptions_uref(),
+        })
+    }
+
+    /// Installs the taxed ERC20 contract with the default set of entry points.
+    ///
+    /// This should be called from within `fn call()` of your contract. The installing account
+    /// receives the initial supply and becomes the owner allowed to change the tax settings.
+    pub fn install(
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_supply: U256,
+        tax_per_mille: u16,
+        tax_distributor: Address,
+        distribution_threshold: U256,
+    ) -> Result<TaxedERC20, Error> {
+        let default_entry_points = entry_points::default();
+        TaxedERC20::install_custom(
+            name,
+            symbol,
+            decimals,
+            initial_supply,
+            tax_per_mille,
+            tax_distributor,
+            distribution_threshold,
+            TAXED_ERC20_TOKEN_CONTRACT_KEY_NAME,
+            default_entry_points,
+        )
+    }
+
+    /// Returns the underlying ERC20 implementation, for the untaxed entry points.
+    pub fn erc20(&mut self) -> &mut ERC20 {
+        &mut self.erc20
+    }
+
+    /// Returns the owner of the token.
+    pub fn owner(&self) -> Address {
+        detail::read_from(OWNER_KEY_NAME)
+    }
+
+    /// Returns the transfer tax, in per mille of the transferred amount.
+    pub fn tax_per_mille(&self) -> u16 {
+        detail::read_from(TAX_PER_MILLE_KEY_NAME)
+    }
+
+    /// Returns the tax distributor credited with the tax.
+    pub fn tax_distributor(&self) -> Address {
+        detail::read_from(TAX_DISTRIBUTOR_KEY_NAME)
+    }
+
+    /// Returns the distributable tax distributor balance that triggers `distribute_tax`, zero if
+    /// never.
+    pub fn distribution_threshold(&self) -> U256 {
+        detail::read_from(DISTRIBUTION_THRESHOLD_KEY_NAME)
+    }
+
+    /// Returns the tax exemption of `address`.
+    pub fn exemption(&self, address: Address) -> Exemption {
+        detail::read_dictionary(self.exemptions_uref(), address).unwrap_or_default()
+    }
+
+    /// Transfers `amount` of tokens from the direct caller to `recipient`, minus the tax.
+    pub fn transfer(&mut self, recipient: Address, amount: U256) -> Result<(), Error> {
+        let mut hook = self.tax_hook()?;
+        self.erc20
+            .transfer_with_hook(recipient, amount, &mut hook)?;
+        Ok(())
+    }
+
+    /// Transfers `amount` of tokens from `owner` to `recipient`, minus the tax, spending the
+    /// direct caller's allowance.
+    pub fn transfer_from(
+        &mut self,
+        owner: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let mut hook = self.tax_hook()?;
+        self.erc20
+            .transfer_from_with_hook(owner, recipient, amount, &mut hook)?;
+        Ok(())
+    }
+
+    /// Burns `amount` of the direct caller's tokens.
+    pub fn burn(&mut self, amount: U256) -> Result<(), Error> {
+        let owner = detail::get_immediate_caller_address()?;
+        self.erc20.burn(owner, amount)?;
+        Ok(())
+    }
+
+    /// Sets the transfer tax, in per mille.
+    pub fn set_tax_per_mille(&mut self, tax_per_mille: u16) -> Result<(), Error> {
+        self.only_owner()?;
+        if tax_per_mille > MAX_TAX_PER_MILLE {
+            return Err(Error::InvalidTax);
+        }
+        detail::write_to(TAX_PER_MILLE_KEY_NAME, tax_per_mille);
+        Ok(())
+    }
+
+    /// Sets whether transfers from and to `address` are exempt from the tax.
+    ///
+    /// Meant for exchanges, bridge pools and similar contracts moving tokens on behalf of users.
+    /// The tax distributor is always exempt.
+    pub fn set_exemption(&mut self, address: Address, exemption: Exemption) -> Result<(), Error> {
+        self.only_owner()?;
+        detail::write_dictionary(self.exemptions_uref(), address, exemption);
+        Ok(())
+    }
+
+    /// Sets the tax distributor credited with the tax.
+    pub fn set_tax_distributor(&mut self, tax_distributor: Address) -> Result<(), Error> {
+        self.only_owner()?;
+        if tax_distributor.as_contract_package_hash().is_none() {
+            return Err(Error::BadTaxDistributor);
+        }
+        detail::write_to(TAX_DISTRIBUTOR_KEY_NAME, tax_distributor);
+        Ok(())
+    }
+
+    /// Sets the distributable tax distributor balance that triggers `distribute_tax`. Zero
+    /// disables it.
+    ///
+    /// Nothing is triggered while the tax distributor has no distribution for this token.
+    pub fn set_distribution_threshold(
+        &mut self,
+        distribution_threshold: U256,
+    ) -> Result<(), Error> {
+        self.only_owner()?;
+        detail::write_to(DISTRIBUTION_THRESHOLD_KEY_NAME, distribution_threshold);
+        Ok(())
+    }
+
+    /// Installs the taxed ERC20 contract with a custom set of entry points.
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn install_custom(
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_supply: U256,
+        tax_per_mille: u16,
+        tax_distributor: Address,
+        distribution_threshold: U256,
+        contract_key_name: &str,
+        entry_points: EntryPoints,
+    ) -> Result<TaxedERC20, Error> {
+        if tax_per_mille > MAX_TAX_PER_MILLE {
+            return Err(Error::InvalidTax);
+        }
+        if tax_distributor.as_contract_package_hash().is_none() {
+            return Err(Error::BadTaxDistributor);
+        }
+        let owner = detail::get_caller_address()?;
+        let exemptions_uref = storage::new_dictionary(EXEMPTIONS_KEY_NAME).unwrap_or_revert();
+
+        let mut named_keys = NamedKeys::new();
+
+        let owner_key = Key::from(storage::new_uref(owner).into_read());
+        let tax_per_mille_key = Key::from(storage::new_uref(tax_per_mille).into_read_write());
+        let tax_distributor_key = Key::from(storage::new_uref(tax_distributor).into_read_write());
+        let distribution_threshold_key =
+            Key::from(storage::new_uref(distribution_threshold).into_read_write());
+
+        named_keys.insert(OWNER_KEY_NAME.to_string(), owner_key);
+        named_keys.insert(TAX_PER_MILLE_KEY_NAME.to_string(), tax_per_mille_key);
+        named_keys.insert(TAX_DISTRIBUTOR_KEY_NAME.to_string(), tax_distributor_key);
+        named_keys.insert(
+            DISTRIBUTION_THRESHOLD_KEY_NAME.to_string(),
+            distribution_threshold_key,
+        );
+
+        runtime::remove_key(EXEMPTIONS_KEY_NAME);
+        named_keys.insert(EXEMPTIONS_KEY_NAME.to_string(), Key::from(exemptions_uref));
+
+        let erc20 = ERC20::install_custom_with_named_keys(
+            name,
+            symbol,
+            decimals,
+            initial_supply,
+            contract_key_name,
+            entry_points,
+            named_keys,
+        )?;
+        Ok(TaxedERC20::new(erc20, exemptions_uref))
+    }
+}
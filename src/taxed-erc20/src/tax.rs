@@ -0,0 +1,108 @@
+//! Transfer hook charging the token tax.
+use casper_contract::contract_api::runtime;
+use casper_erc20::{Address, Error, TransferHook};
+use casper_types::{runtime_args, RuntimeArgs, URef, U256};
+
+use crate::{
+    constants::{
+        DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME, DISTRIBUTE_TAX_ENTRY_POINT_NAME,
+        TOKEN_RUNTIME_ARG_NAME,
+    },
+    detail,
+    exemption::Exemption,
+};
+
+/// Hook deducting `tax_per_mille` of every transfer and crediting it to the tax distributor.
+///
+/// Transfers from or to the tax distributor are not taxed, so its payouts are not taxed again.
+/// Neither are transfers from addresses exempt as sender or to addresses exempt as recipient.
+pub(crate) struct TaxHook {
+    pub(crate) token: Address,
+    pub(crate) tax_per_mille: u16,
+    pub(crate) tax_distributor: Address,
+    pub(crate) distribution_threshold: U256,
+    pub(crate) exemptions_uref: URef,
+}
+
+impl TaxHook {
+    fn exemption(&self, address: Address) -> Exemption {
+        detail::read_dictionary(self.exemptions_uref, address).unwrap_or_default()
+    }
+
+    fn is_exempt(&self, sender: Address, recipient: Address) -> bool {
+        sender == self.tax_distributor
+            || recipient == self.tax_distributor
+            || self.exemption(sender).as_sender
+            || self.exemption(recipient).as_recipient
+    }
+
+    /// Returns the tax distributor's balance of this token that is not waiting to be claimed,
+    /// or zero if it has no distribution for this token.
+    fn distributable_balance(&self) -> U256 {
+        match self.tax_distributor.as_contract_package_hash() {
+            Some(contract_package_hash) => runtime::call_versioned_contract(
+                *contract_package_hash,
+                None,
+                DISTRIBUTABLE_BALANCE_ENTRY_POINT_NAME,
+                runtime_args! {
+                    TOKEN_RUNTIME_ARG_NAME => self.token,
+                },
+            ),
+            None => U256::zero(),
+        }
+    }
+
+    /// Asks the tax distributor to distribute its balance of this token.
+    fn distribute_tax(&self) {
+        if let Some(contract_package_hash) = self.tax_distributor.as_contract_package_hash() {
+            runtime::call_versioned_contract::<bool>(
+                *contract_package_hash,
+                None,
+                DISTRIBUTE_TAX_ENTRY_POINT_NAME,
+                runtime_args! {
+                    TOKEN_RUNTIME_ARG_NAME => self.token,
+                },
+            );
+        }
+    }
+}
+
+impl TransferHook for TaxHook {
+    fn transfer_fee(
+        &mut self,
+        sender: Address,
+        recipient: Address,
+        amount: U256,
+    ) -> Result<Option<(Address, U256)>, Error> {
+        if self.tax_per_mille == 0 || self.is_exempt(sender, recipient) {
+            return Ok(None);
+        }
+        let tax = amount
+            .checked_mul(U256::from(self.tax_per_mille))
+            .ok_or(Error::Overflow)?
+            / U256::from(1000);
+        Ok(Some((self.tax_distributor, tax)))
+    }
+
+    /// Triggers `distribute_tax` once the tax distributor's distributable balance reaches the
+    /// distribution threshold. A zero threshold never triggers it.
+    ///
+    /// The distributable balance leaves out amounts waiting to be claimed and is zero while the
+    /// tax distributor has no distribution for this token, so unclaimed payouts or a missing
+    /// distribution cannot make every taxed transfer revert.
+    fn after_transfer(
+        &mut self,
+        _sender: Address,
+        _recipient: Address,
+        _amount: U256,
+        fee: U256,
+    ) -> Result<(), Error> {
+        if fee.is_zero() || self.distribution_threshold.is_zero() {
+            return Ok(());
+        }
+        if self.distributable_balance() >= self.distribution_threshold {
+            self.distribute_tax();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_erc20::{
+    constants::{
+        ADDRESS_RUNTIME_ARG_NAME, AMOUNT_RUNTIME_ARG_NAME, DECIMALS_RUNTIME_ARG_NAME,
+        NAME_RUNTIME_ARG_NAME, OWNER_RUNTIME_ARG_NAME, RECIPIENT_RUNTIME_ARG_NAME,
+        SPENDER_RUNTIME_ARG_NAME, SYMBOL_RUNTIME_ARG_NAME, TOTAL_SUPPLY_RUNTIME_ARG_NAME,
+    },
+    Address,
+};
+use casper_taxed_erc20::{
+    constants::{
+        AS_RECIPIENT_RUNTIME_ARG_NAME, AS_SENDER_RUNTIME_ARG_NAME,
+        DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME, TAX_DISTRIBUTOR_RUNTIME_ARG_NAME,
+        TAX_PER_MILLE_RUNTIME_ARG_NAME,
+    },
+    Exemption, TaxedERC20,
+};
+use casper_types::{CLValue, U256};
+
+#[no_mangle]
+pub extern "C" fn name() {
+    let name = TaxedERC20::default().erc20().name();
+    runtime::ret(CLValue::from_t(name).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn symbol() {
+    let symbol = TaxedERC20::default().erc20().symbol();
+    runtime::ret(CLValue::from_t(symbol).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn decimals() {
+    let decimals = TaxedERC20::default().erc20().decimals();
+    runtime::ret(CLValue::from_t(decimals).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn total_supply() {
+    let total_supply = TaxedERC20::default().erc20().total_supply();
+    runtime::ret(CLValue::from_t(total_supply).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+    let balance = TaxedERC20::default().erc20().balance_of(address);
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient: Address = runtime::get_named_arg(RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default()
+        .transfer(recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn approve() {
+    let spender: Address = runtime::get_named_arg(SPENDER_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default()
+        .erc20()
+        .approve(spender, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn allowance() {
+    let owner: Address = runtime::get_named_arg(OWNER_RUNTIME_ARG_NAME);
+    let spender: Address = runtime::get_named_arg(SPENDER_RUNTIME_ARG_NAME);
+    let val = TaxedERC20::default().erc20().allowance(owner, spender);
+    runtime::ret(CLValue::from_t(val).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let owner: Address = runtime::get_named_arg(OWNER_RUNTIME_ARG_NAME);
+    let recipient: Address = runtime::get_named_arg(RECIPIENT_RUNTIME_ARG_NAME);
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+    TaxedERC20::default()
+        .transfer_from(owner, recipient, amount)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn burn() {
+    let amount: U256 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default().burn(amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn tax_per_mille() {
+    let tax_per_mille = TaxedERC20::default().tax_per_mille();
+    runtime::ret(CLValue::from_t(tax_per_mille).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn tax_distributor() {
+    let tax_distributor = TaxedERC20::default().tax_distributor();
+    runtime::ret(CLValue::from_t(tax_distributor).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn distribution_threshold() {
+    let distribution_threshold = TaxedERC20::default().distribution_threshold();
+    runtime::ret(CLValue::from_t(distribution_threshold).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn exemption() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+    let exemption = TaxedERC20::default().exemption(address);
+    runtime::ret(CLValue::from_t(exemption).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_exemption() {
+    let address: Address = runtime::get_named_arg(ADDRESS_RUNTIME_ARG_NAME);
+    let exemption = Exemption {
+        as_sender: runtime::get_named_arg(AS_SENDER_RUNTIME_ARG_NAME),
+        as_recipient: runtime::get_named_arg(AS_RECIPIENT_RUNTIME_ARG_NAME),
+    };
+
+    TaxedERC20::default()
+        .set_exemption(address, exemption)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_tax_per_mille() {
+    let tax_per_mille: u16 = runtime::get_named_arg(TAX_PER_MILLE_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default()
+        .set_tax_per_mille(tax_per_mille)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_tax_distributor() {
+    let tax_distributor: Address = runtime::get_named_arg(TAX_DISTRIBUTOR_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default()
+        .set_tax_distributor(tax_distributor)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn set_distribution_threshold() {
+    let distribution_threshold: U256 =
+        runtime::get_named_arg(DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME);
+
+    TaxedERC20::default()
+        .set_distribution_threshold(distribution_threshold)
+        .unwrap_or_revert();
+}
+
+#[no_mangle]
+fn call() {
+    let name: String = runtime::get_named_arg(NAME_RUNTIME_ARG_NAME);
+    let symbol: String = runtime::get_named_arg(SYMBOL_RUNTIME_ARG_NAME);
+    let decimals = runtime::get_named_arg(DECIMALS_RUNTIME_ARG_NAME);
+    let total_supply = runtime::get_named_arg(TOTAL_SUPPLY_RUNTIME_ARG_NAME);
+    let tax_per_mille = runtime::get_named_arg(TAX_PER_MILLE_RUNTIME_ARG_NAME);
+    let tax_distributor = runtime::get_named_arg(TAX_DISTRIBUTOR_RUNTIME_ARG_NAME);
+    let distribution_threshold = runtime::get_named_arg(DISTRIBUTION_THRESHOLD_RUNTIME_ARG_NAME);
+
+    let _token = TaxedERC20::install(
+        name,
+        symbol,
+        decimals,
+        total_supply,
+        tax_per_mille,
+        tax_distributor,
+        distribution_threshold,
+    )
+    .unwrap_or_revert();
+}